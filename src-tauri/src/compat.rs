@@ -0,0 +1,91 @@
+//! Backend version compatibility check.
+//!
+//! The GUI declares a semver range it is known to work with; the
+//! backend reports its version through `/health`. Out-of-range (or
+//! unknown) versions emit a `"version-mismatch"` event so the frontend
+//! can warn the user — commands keep working either way, since a
+//! version skew usually degrades rather than breaks.
+
+use semver::{Version, VersionReq};
+use serde::Serialize;
+use tauri::Emitter;
+
+use crate::bridge::Bridge;
+use crate::error::AppError;
+
+/// Presenter versions this GUI is tested against.
+pub const SUPPORTED_RANGE: &str = ">=0.4.0, <0.6.0";
+
+/// Result of comparing the backend version against [`SUPPORTED_RANGE`].
+#[derive(Debug, Clone, Serialize)]
+pub struct CompatibilityReport {
+    /// Version the backend reported, if it was reachable.
+    pub backend_version: Option<String>,
+    pub supported_range: String,
+    pub compatible: bool,
+}
+
+fn report_for(backend_version: Option<String>) -> CompatibilityReport {
+    let range = VersionReq::parse(SUPPORTED_RANGE).expect("SUPPORTED_RANGE is valid semver");
+    let compatible = backend_version
+        .as_deref()
+        .and_then(|v| Version::parse(v).ok())
+        .is_some_and(|v| range.matches(&v));
+    CompatibilityReport {
+        backend_version,
+        supported_range: SUPPORTED_RANGE.to_string(),
+        compatible,
+    }
+}
+
+/// Fetch the backend version and compare it against the supported
+/// range. An unreachable backend or unparseable version counts as
+/// incompatible so the frontend errs on the side of warning.
+#[tauri::command]
+pub async fn check_compatibility(
+    bridge: tauri::State<'_, Bridge>,
+) -> Result<CompatibilityReport, AppError> {
+    Ok(report_for(bridge.health().await.version))
+}
+
+/// Probe the backend and emit `"version-mismatch"` if it is reachable
+/// but out of range. Called at startup and whenever the backend comes
+/// (back) up; quiet when everything matches.
+pub async fn check_and_warn<R: tauri::Runtime, M: tauri::Manager<R> + Emitter<R>>(app: &M) {
+    let bridge = app.state::<Bridge>();
+    let health = bridge.health().await;
+    if !health.reachable {
+        return;
+    }
+    let report = report_for(health.version);
+    if !report.compatible {
+        tracing::warn!(
+            backend_version = report.backend_version.as_deref().unwrap_or("unknown"),
+            supported_range = SUPPORTED_RANGE,
+            "backend version outside the supported range"
+        );
+        let _ = app.emit("version-mismatch", &report);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_range_version_is_compatible() {
+        assert!(report_for(Some("0.5.2".into())).compatible);
+    }
+
+    #[test]
+    fn out_of_range_version_is_incompatible() {
+        assert!(!report_for(Some("0.6.0".into())).compatible);
+        assert!(!report_for(Some("0.3.9".into())).compatible);
+    }
+
+    #[test]
+    fn missing_or_garbage_version_is_incompatible() {
+        assert!(!report_for(None).compatible);
+        assert!(!report_for(Some("not-a-version".into())).compatible);
+    }
+}
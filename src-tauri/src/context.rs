@@ -0,0 +1,110 @@
+//! File-context classification: "summarize this", "run the script in
+//! foo.sh" and friends need the file contents alongside the utterance.
+//!
+//! Files are read locally with a hard total-size cap, must decode as
+//! UTF-8, and must live under the user's home directory so a crafted
+//! request can't exfiltrate arbitrary system files.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::bridge::{Bridge, IntentResult};
+use crate::error::AppError;
+
+/// Total bytes of file content attached to one request.
+const MAX_TOTAL_BYTES: u64 = 1024 * 1024;
+
+/// One attached file as sent to the backend.
+#[derive(Debug, Serialize)]
+struct FileContext {
+    path: PathBuf,
+    contents: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ContextClassifyRequest<'a> {
+    text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    files: Vec<FileContext>,
+}
+
+/// Directories attachments may come from.
+fn allowed_roots() -> Vec<PathBuf> {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .into_iter()
+        .collect()
+}
+
+fn check_allowed(path: &Path) -> Result<PathBuf, AppError> {
+    let canonical = path.canonicalize().map_err(|e| {
+        AppError::InvalidInput(format!("cannot read {}: {e}", path.display()))
+    })?;
+    if !allowed_roots().iter().any(|root| canonical.starts_with(root)) {
+        return Err(AppError::PolicyDenied(format!(
+            "{} is outside the allowed directories",
+            path.display()
+        )));
+    }
+    Ok(canonical)
+}
+
+fn read_context_files(files: &[PathBuf]) -> Result<Vec<FileContext>, AppError> {
+    let mut total: u64 = 0;
+    let mut out = Vec::with_capacity(files.len());
+    for path in files {
+        let canonical = check_allowed(path)?;
+        let bytes = std::fs::read(&canonical).map_err(|e| {
+            AppError::InvalidInput(format!("cannot read {}: {e}", path.display()))
+        })?;
+        total += bytes.len() as u64;
+        if total > MAX_TOTAL_BYTES {
+            return Err(AppError::InvalidInput(format!(
+                "attached files exceed the {MAX_TOTAL_BYTES} byte limit"
+            )));
+        }
+        let contents = String::from_utf8(bytes).map_err(|_| {
+            AppError::InvalidInput(format!(
+                "{} is not valid UTF-8 text; binary files cannot be attached",
+                path.display()
+            ))
+        })?;
+        out.push(FileContext {
+            path: canonical,
+            contents,
+        });
+    }
+    Ok(out)
+}
+
+/// Classify `text` with the contents of the referenced files attached
+/// as extra context.
+#[tauri::command]
+pub async fn classify_with_context(
+    text: String,
+    files: Vec<PathBuf>,
+    bridge: tauri::State<'_, Bridge>,
+    models: tauri::State<'_, crate::models::ModelState>,
+    online: tauri::State<'_, crate::offline::OnlineState>,
+    settings: tauri::State<'_, crate::settings::SettingsStore>,
+    gate: tauri::State<'_, crate::limit::BackendGate>,
+) -> Result<IntentResult, AppError> {
+    online.guard()?;
+    let text = crate::input::validate_user_input(&text, settings.get().max_input_chars)?;
+    let _slot = gate.acquire().await?;
+    let attached = read_context_files(&files)?;
+    let result = bridge
+        .post_idempotent(
+            "/classify",
+            &ContextClassifyRequest {
+                text: &text,
+                model: models.active(),
+                files: attached,
+            },
+        )
+        .await;
+    online.observe(&result);
+    result
+}
@@ -0,0 +1,92 @@
+//! `tinyllama-x://` deep links.
+//!
+//! `tinyllama-x://classify?text=deploy%20staging` launched from a
+//! browser or script prefills the input: the URL's host is the action,
+//! the query string becomes a params map, and the frontend receives a
+//! `"deep-link"` event with `{ action, params }`. Cold starts are
+//! covered because the plugin replays URLs received before the webview
+//! was ready; warm starts focus the existing window first. Malformed
+//! URLs are logged and dropped — a bad link should never crash the app.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+/// Payload for `"deep-link"` events.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeepLinkPayload {
+    pub action: String,
+    pub params: HashMap<String, String>,
+}
+
+/// Parse one deep-link URL into action + params, or `None` when it
+/// isn't a well-formed `tinyllama-x://` link.
+fn parse(raw: &str) -> Option<DeepLinkPayload> {
+    let url = url::Url::parse(raw).ok()?;
+    if url.scheme() != "tinyllama-x" {
+        return None;
+    }
+    let action = url.host_str()?.to_string();
+    if action.is_empty() {
+        return None;
+    }
+    let params = url
+        .query_pairs()
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    Some(DeepLinkPayload { action, params })
+}
+
+fn handle(app: &AppHandle, urls: &[url::Url]) {
+    for raw in urls {
+        let Some(payload) = parse(raw.as_str()) else {
+            tracing::warn!(url = %raw, "ignoring malformed deep link");
+            continue;
+        };
+        // Warm start: bring the existing window to the front so the
+        // prefilled input is actually visible.
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        let _ = app.emit("deep-link", &payload);
+    }
+}
+
+/// Register the scheme handler. Called once from setup; URLs that
+/// arrived before setup (cold start) are replayed via `get_current`.
+pub fn init(app: &AppHandle) -> Result<(), tauri::Error> {
+    let handler = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        handle(&handler, event.urls().as_slice());
+    });
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        handle(app, &urls);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_action_and_params() {
+        let payload = parse("tinyllama-x://classify?text=deploy%20staging").unwrap();
+        assert_eq!(payload.action, "classify");
+        assert_eq!(payload.params["text"], "deploy staging");
+    }
+
+    #[test]
+    fn rejects_foreign_scheme() {
+        assert!(parse("https://example.com/classify").is_none());
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(parse("not a url").is_none());
+        assert!(parse("tinyllama-x://").is_none());
+    }
+}
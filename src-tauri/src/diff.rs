@@ -0,0 +1,219 @@
+//! Predicted before/after diffs for file-modifying plans.
+//!
+//! Simulation can't run the command, but for the simple file-writing
+//! binaries we support (`rm`, `cp`, `mv`) the outcome is predictable
+//! enough to diff: a removal diffs against empty, a copy/move diffs
+//! the destination against the source. Anything else is skipped rather
+//! than guessed. Files over the size cap are omitted to keep the
+//! payload bounded, and binary files are flagged instead of producing
+//! garbage text hunks.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+
+use crate::plan::Plan;
+
+/// Per-file payload cap; larger files are skipped entirely.
+const MAX_DIFF_FILE_BYTES: u64 = 256 * 1024;
+
+/// One unified-diff hunk: the `@@` header plus prefixed lines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffHunk {
+    pub header: String,
+    /// Lines prefixed with `+`, `-` or a space, as in `diff -u`.
+    pub lines: Vec<String>,
+}
+
+/// Predicted change to one file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDiff {
+    pub path: PathBuf,
+    /// True when the file isn't valid UTF-8 text; no hunks then.
+    pub binary: bool,
+    pub hunks: Vec<DiffHunk>,
+}
+
+enum Content {
+    Missing,
+    Binary,
+    TooLarge,
+    Text(String),
+}
+
+fn read_content(path: &Path) -> Content {
+    match std::fs::metadata(path) {
+        Ok(meta) if meta.len() > MAX_DIFF_FILE_BYTES => return Content::TooLarge,
+        Ok(_) => {}
+        Err(_) => return Content::Missing,
+    }
+    match std::fs::read(path) {
+        Ok(bytes) => match String::from_utf8(bytes) {
+            Ok(text) => Content::Text(text),
+            Err(_) => Content::Binary,
+        },
+        Err(_) => Content::Missing,
+    }
+}
+
+fn hunks_between(old: &str, new: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(old, new);
+    let unified = diff.unified_diff();
+    unified
+        .iter_hunks()
+        .map(|hunk| DiffHunk {
+            header: hunk.header().to_string(),
+            lines: hunk
+                .iter_changes()
+                .map(|change| format!("{}{}", change.tag(), change.value().trim_end_matches('\n')))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Diff one file from its current content to a predicted new content.
+/// `None` when there is nothing meaningful to show (unchanged, too
+/// large, or missing on both sides).
+fn diff_file(path: &Path, new: Option<&str>) -> Option<FileDiff> {
+    let old = match read_content(path) {
+        Content::TooLarge => return None,
+        Content::Binary => {
+            return Some(FileDiff {
+                path: path.to_path_buf(),
+                binary: true,
+                hunks: Vec::new(),
+            })
+        }
+        Content::Missing => String::new(),
+        Content::Text(text) => text,
+    };
+    let new = new.unwrap_or("");
+    if old == new {
+        return None;
+    }
+    Some(FileDiff {
+        path: path.to_path_buf(),
+        binary: false,
+        hunks: hunks_between(&old, new),
+    })
+}
+
+/// Resolve a path argument the way execution will: relative arguments
+/// are interpreted against the sandbox root the command runs in, not
+/// the app process's own cwd.
+fn resolve(base: &Path, arg: &str) -> PathBuf {
+    let path = Path::new(arg);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base.join(path)
+    }
+}
+
+/// Predicted diffs for the files a plan would modify, where the
+/// outcome is predictable. Relative arguments resolve against `base`
+/// (the sandbox root the plan will run in). Unsupported commands
+/// return no diffs.
+pub fn predicted_diffs(plan: &Plan, base: &Path) -> Vec<FileDiff> {
+    let bin = plan.command.rsplit('/').next().unwrap_or(&plan.command);
+    let paths: Vec<PathBuf> = plan
+        .args
+        .iter()
+        .filter(|a| !a.starts_with('-'))
+        .map(|a| resolve(base, a))
+        .collect();
+    let mut out = Vec::new();
+    match bin {
+        "rm" => {
+            for path in &paths {
+                out.extend(diff_file(path, None));
+            }
+        }
+        "cp" | "mv" => {
+            if let [src, dst] = paths.as_slice() {
+                let new = match read_content(src) {
+                    Content::Text(text) => Some(text),
+                    Content::Binary => {
+                        out.push(FileDiff {
+                            path: dst.clone(),
+                            binary: true,
+                            hunks: Vec::new(),
+                        });
+                        None
+                    }
+                    _ => None,
+                };
+                if let Some(new) = new {
+                    out.extend(diff_file(dst, Some(&new)));
+                }
+                if bin == "mv" {
+                    // The source disappears on a move.
+                    out.extend(diff_file(src, None));
+                }
+            }
+        }
+        _ => {}
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tinyllama-x-diff-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn plan(command: &str, args: &[&str]) -> Plan {
+        Plan {
+            id: "p1".into(),
+            description: "test".into(),
+            command: command.into(),
+            args: args.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn rm_diffs_against_empty() {
+        let dir = scratch("rm");
+        let file = dir.join("a.txt");
+        std::fs::write(&file, "one\ntwo\n").unwrap();
+        let diffs = predicted_diffs(&plan("rm", &[file.to_str().unwrap()]), Path::new("/"));
+        assert_eq!(diffs.len(), 1);
+        assert!(!diffs[0].binary);
+        let lines: Vec<_> = diffs[0].hunks.iter().flat_map(|h| h.lines.iter()).collect();
+        assert!(lines.contains(&&"-one".to_string()));
+    }
+
+    #[test]
+    fn binary_file_is_flagged_without_hunks() {
+        let dir = scratch("binary");
+        let file = dir.join("blob");
+        std::fs::write(&file, [0u8, 159, 146, 150]).unwrap();
+        let diffs = predicted_diffs(&plan("rm", &[file.to_str().unwrap()]), Path::new("/"));
+        assert_eq!(diffs.len(), 1);
+        assert!(diffs[0].binary);
+        assert!(diffs[0].hunks.is_empty());
+    }
+
+    #[test]
+    fn relative_argument_resolves_against_the_base() {
+        let dir = scratch("relative");
+        std::fs::write(dir.join("notes.txt"), "keep me\n").unwrap();
+        let diffs = predicted_diffs(&plan("rm", &["notes.txt"]), &dir);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].path, dir.join("notes.txt"));
+        let lines: Vec<_> = diffs[0].hunks.iter().flat_map(|h| h.lines.iter()).collect();
+        assert!(lines.contains(&&"-keep me".to_string()));
+    }
+
+    #[test]
+    fn unsupported_command_produces_no_diffs() {
+        assert!(predicted_diffs(&plan("ls", &["-la"]), Path::new("/")).is_empty());
+    }
+}
@@ -0,0 +1,207 @@
+//! Chunked classification for long documents.
+//!
+//! A document far beyond the backend's context window is split into
+//! overlapping chunks, each chunk is classified on its own, and the
+//! per-chunk results are aggregated into one [`IntentResult`]: majority
+//! vote on the intent, the highest confidence among the winning chunks,
+//! and slots merged first-seen-wins. The frontend gets one
+//! `"chunk-progress"` event per completed chunk to drive a progress bar.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tauri::Emitter;
+
+use crate::bridge::{Bridge, IntentResult};
+use crate::error::AppError;
+
+/// Fraction of each chunk repeated at the start of the next, so an
+/// utterance straddling a boundary is still seen whole by one chunk.
+const OVERLAP_DIVISOR: usize = 4;
+
+/// Payload for `"chunk-progress"` events.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChunkProgress {
+    /// 1-based index of the chunk that just finished.
+    pub chunk: u32,
+    pub total: u32,
+    /// Intent the backend assigned to this chunk.
+    pub intent: String,
+}
+
+/// Split `text` into chunks of `chunk_size` characters, each overlapping
+/// the previous one by `overlap` characters. Boundaries are character
+/// boundaries, never byte offsets, so multi-byte input stays intact.
+fn chunk_text(text: &str, chunk_size: usize, overlap: usize) -> Vec<String> {
+    // The overlap must leave the window room to advance.
+    debug_assert!(overlap < chunk_size);
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= chunk_size {
+        return vec![text.to_string()];
+    }
+    let stride = chunk_size - overlap;
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let end = (start + chunk_size).min(chars.len());
+        chunks.push(chars[start..end].iter().collect());
+        if end == chars.len() {
+            break;
+        }
+        start += stride;
+    }
+    chunks
+}
+
+/// Fold per-chunk results into one: the intent most chunks agreed on,
+/// the best confidence any winning chunk reported, and every slot from
+/// every chunk with earlier chunks taking precedence on conflicts.
+fn aggregate(results: &[IntentResult]) -> IntentResult {
+    let mut votes: HashMap<&str, usize> = HashMap::new();
+    for result in results {
+        *votes.entry(result.intent.as_str()).or_default() += 1;
+    }
+    // Ties break toward the more confident intent.
+    let winner = votes
+        .iter()
+        .max_by(|(a_intent, a_votes), (b_intent, b_votes)| {
+            let best = |intent: &str| {
+                results
+                    .iter()
+                    .filter(|r| r.intent == intent)
+                    .map(|r| r.confidence)
+                    .fold(0.0_f32, f32::max)
+            };
+            a_votes
+                .cmp(b_votes)
+                .then(best(a_intent).total_cmp(&best(b_intent)))
+        })
+        .map(|(intent, _)| intent.to_string())
+        .unwrap_or_default();
+
+    let confidence = results
+        .iter()
+        .filter(|r| r.intent == winner)
+        .map(|r| r.confidence)
+        .fold(0.0_f32, f32::max);
+
+    let mut slots = HashMap::new();
+    for result in results {
+        for (key, value) in &result.slots {
+            slots.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+
+    IntentResult {
+        intent: winner,
+        confidence,
+        slots,
+        cached: false,
+    }
+}
+
+/// Classify a long document by chunking it and aggregating the
+/// per-chunk intents.
+///
+/// Emits one `"chunk-progress"` event as each chunk completes. Input
+/// that fits in a single chunk is classified in one call, exactly like
+/// `classify_intent` on the same text.
+#[tauri::command]
+#[tracing::instrument(skip_all, fields(chunk_size))]
+pub async fn classify_document(
+    text: String,
+    chunk_size: usize,
+    window: tauri::Window,
+    bridge: tauri::State<'_, Bridge>,
+    models: tauri::State<'_, crate::models::ModelState>,
+    metrics: tauri::State<'_, crate::metrics::Metrics>,
+    online: tauri::State<'_, crate::offline::OnlineState>,
+    gate: tauri::State<'_, crate::limit::BackendGate>,
+) -> Result<IntentResult, AppError> {
+    online.guard()?;
+    if chunk_size == 0 {
+        return Err(AppError::InvalidInput("chunk_size must be positive".into()));
+    }
+    if text.trim().is_empty() {
+        return Err(AppError::InvalidInput("input is empty".into()));
+    }
+    let _slot = gate.acquire().await?;
+    let model = models.active();
+    let overlap = chunk_size / OVERLAP_DIVISOR;
+    let chunks = chunk_text(&text, chunk_size, overlap);
+    let total = chunks.len() as u32;
+
+    let work = async {
+        let mut results = Vec::with_capacity(chunks.len());
+        for (idx, chunk) in chunks.iter().enumerate() {
+            let result = bridge.classify(chunk, model.clone()).await?;
+            let _ = window.emit(
+                "chunk-progress",
+                ChunkProgress {
+                    chunk: idx as u32 + 1,
+                    total,
+                    intent: result.intent.clone(),
+                },
+            );
+            results.push(result);
+        }
+        Ok(aggregate(&results))
+    };
+    let result = crate::metrics::timed(&metrics, "classify_document", work).await;
+    online.observe(&result);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(intent: &str, confidence: f32) -> IntentResult {
+        IntentResult {
+            intent: intent.into(),
+            confidence,
+            slots: HashMap::new(),
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn short_input_is_a_single_chunk() {
+        let chunks = chunk_text("hello", 100, 25);
+        assert_eq!(chunks, vec!["hello".to_string()]);
+    }
+
+    #[test]
+    fn chunks_overlap_and_cover_the_whole_text() {
+        let text = "abcdefghij";
+        let chunks = chunk_text(text, 4, 1);
+        assert_eq!(chunks, vec!["abcd", "defg", "ghij"]);
+    }
+
+    #[test]
+    fn chunking_respects_char_boundaries() {
+        let text = "héllo wörld, héllo wörld";
+        let chunks = chunk_text(text, 5, 1);
+        assert!(chunks.concat().len() >= text.len());
+        for chunk in chunks {
+            assert!(chunk.chars().count() <= 5);
+        }
+    }
+
+    #[test]
+    fn aggregate_takes_majority_vote_and_max_confidence() {
+        let mut a = result("package_install", 0.6);
+        a.slots.insert("package".into(), "ripgrep".into());
+        let mut b = result("package_install", 0.9);
+        b.slots.insert("package".into(), "fd".into());
+        b.slots.insert("manager".into(), "apt".into());
+        let c = result("file_search", 0.95);
+
+        let merged = aggregate(&[a, b, c]);
+        assert_eq!(merged.intent, "package_install");
+        assert!((merged.confidence - 0.9).abs() < f32::EPSILON);
+        // First chunk's value wins on conflicting slots.
+        assert_eq!(merged.slots["package"], "ripgrep");
+        assert_eq!(merged.slots["manager"], "apt");
+    }
+}
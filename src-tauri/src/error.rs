@@ -0,0 +1,126 @@
+//! Structured application errors.
+//!
+//! Commands return `Result<T, AppError>`; Tauri serializes the error to
+//! the frontend as `{ code, message }` so it can branch on the
+//! machine-readable code while still having a human-readable message.
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("backend unreachable: {0}")]
+    BackendUnreachable(String),
+
+    #[error("request timed out: {0}")]
+    Timeout(String),
+
+    /// All configured retries failed; distinct from a single hard
+    /// failure so the frontend can suggest checking the backend.
+    #[error("retries exhausted after {attempts} attempts: {last_error}")]
+    RetriesExhausted { attempts: u32, last_error: String },
+
+    #[error("{0}")]
+    PolicyDenied(String),
+
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+
+    #[error("backend returned {status}: {body}")]
+    Upstream { status: u16, body: String },
+
+    #[error("backend rejected credentials; set an API key in settings")]
+    Unauthorized,
+
+    #[error("offline: {0}")]
+    Offline(String),
+
+    /// Too many concurrent backend requests; try again shortly.
+    #[error("backend busy: {0}")]
+    Busy(String),
+
+    #[error("request {0} cancelled")]
+    Cancelled(String),
+
+    #[error("no backup found for plan {0}")]
+    BackupMissing(String),
+
+    #[error("storage error: {0}")]
+    Storage(String),
+
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl AppError {
+    /// Stable machine-readable code for the frontend.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::BackendUnreachable(_) => "backend_unreachable",
+            AppError::Timeout(_) => "timeout",
+            AppError::RetriesExhausted { .. } => "retries_exhausted",
+            AppError::PolicyDenied(_) => "policy_denied",
+            AppError::InvalidInput(_) => "invalid_input",
+            AppError::Upstream { .. } => "upstream",
+            AppError::Unauthorized => "unauthorized",
+            AppError::Offline(_) => "offline",
+            AppError::Busy(_) => "busy",
+            AppError::Cancelled(_) => "cancelled",
+            AppError::BackupMissing(_) => "backup_missing",
+            AppError::Storage(_) => "storage",
+            AppError::Internal(_) => "internal",
+        }
+    }
+
+    /// Variant-specific detail text, without the category prefix the
+    /// locale template supplies.
+    fn detail(&self) -> String {
+        match self {
+            AppError::BackendUnreachable(s)
+            | AppError::PolicyDenied(s)
+            | AppError::InvalidInput(s)
+            | AppError::Offline(s)
+            | AppError::Busy(s)
+            | AppError::Storage(s)
+            | AppError::Internal(s)
+            | AppError::Timeout(s) => s.clone(),
+            AppError::RetriesExhausted {
+                attempts,
+                last_error,
+            } => format!("{last_error} ({attempts} attempts)"),
+            AppError::Upstream { status, body } => format!("{status}: {body}"),
+            AppError::Unauthorized => String::new(),
+            AppError::Cancelled(id) => format!("request {id}"),
+            AppError::BackupMissing(id) => format!("plan {id}"),
+        }
+    }
+
+    /// Map a transport-level failure, distinguishing timeouts from
+    /// plain connection failures.
+    pub fn from_reqwest(url: &str, e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            AppError::Timeout(format!("{url}: {e}"))
+        } else {
+            AppError::BackendUnreachable(format!("{url}: {e}"))
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("AppError", 2)?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field(
+            "message",
+            &crate::i18n::error_message(self.code(), &self.detail()),
+        )?;
+        s.end()
+    }
+}
+
+impl From<crate::allowlist::PolicyError> for AppError {
+    fn from(e: crate::allowlist::PolicyError) -> Self {
+        AppError::PolicyDenied(e.to_string())
+    }
+}
@@ -0,0 +1,471 @@
+//! Execution of confirmed plans, gated by the allowlist policy.
+
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::allowlist::{self, Allowlist, OneShotGrants, PolicyError};
+use crate::audit::{self, AuditEntry, AuditLog};
+use crate::error::AppError;
+use crate::plan::{self, Plan};
+use crate::rollback::BackupStore;
+use crate::settings::SettingsStore;
+
+/// Outcome of an executed plan, returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionOutcome {
+    pub plan_id: String,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Whether an argument is plausibly a filesystem path rather than a
+/// subcommand, package name or flag value.
+fn looks_like_path(arg: &str) -> bool {
+    arg.starts_with('/') || arg.starts_with("./") || arg.starts_with("../") || arg.contains('/')
+}
+
+/// Resolve `arg` against the sandbox root and reject it if the result
+/// escapes the root.
+///
+/// The nearest existing ancestor is canonicalized before the
+/// containment check, so a symlink inside the sandbox pointing outside
+/// it is caught even when the full path doesn't exist yet.
+fn check_within_sandbox(root: &Path, arg: &str) -> Result<(), AppError> {
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|e| AppError::Internal(format!("sandbox root {}: {e}", root.display())))?;
+    let joined = if Path::new(arg).is_absolute() {
+        PathBuf::from(arg)
+    } else {
+        canonical_root.join(arg)
+    };
+
+    // Walk up to the nearest existing ancestor so canonicalization can
+    // resolve symlinks, keeping the not-yet-existing tail.
+    let mut existing = joined.clone();
+    let mut tail: Vec<std::ffi::OsString> = Vec::new();
+    while !existing.exists() {
+        match (existing.file_name(), existing.parent()) {
+            (Some(name), Some(parent)) => {
+                tail.push(name.to_os_string());
+                existing = parent.to_path_buf();
+            }
+            _ => break,
+        }
+    }
+    let mut resolved = existing
+        .canonicalize()
+        .map_err(|e| AppError::Internal(format!("cannot resolve {arg:?}: {e}")))?;
+    for name in tail.iter().rev() {
+        // A `..` in the non-existing tail can't be resolved, so refuse
+        // it outright rather than guessing.
+        if Path::new(name).components().any(|c| c == Component::ParentDir) {
+            return Err(AppError::PolicyDenied(format!(
+                "path argument {arg:?} escapes the sandbox root {}",
+                canonical_root.display()
+            )));
+        }
+        resolved.push(name);
+    }
+
+    if !resolved.starts_with(&canonical_root) {
+        return Err(AppError::PolicyDenied(format!(
+            "path argument {arg:?} resolves to {} outside the sandbox root {}",
+            resolved.display(),
+            canonical_root.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Kill the child's whole process group, so grandchildren spawned into
+/// the same group go down with it.
+#[cfg(unix)]
+fn kill_group(child: &mut tokio::process::Child) {
+    if let Some(pid) = child.id() {
+        unsafe {
+            libc::killpg(pid as i32, libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_group(child: &mut tokio::process::Child) {
+    let _ = child.start_kill();
+}
+
+/// Spawn the plan's command and wait for it, killing the process group
+/// if `timeout` elapses first. The timeout error carries whatever
+/// stdout/stderr was captured before the kill.
+async fn run_with_timeout(
+    command: &str,
+    args: &[String],
+    cwd: &Path,
+    timeout: std::time::Duration,
+) -> Result<std::process::Output, AppError> {
+    use std::process::Stdio;
+    use tokio::io::AsyncReadExt;
+
+    let mut cmd = tokio::process::Command::new(command);
+    cmd.args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    // New process group, so a timeout kill reaches grandchildren too.
+    #[cfg(unix)]
+    cmd.process_group(0);
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| AppError::Internal(format!("failed to spawn {command}: {e}")))?;
+    let mut stdout_pipe = child.stdout.take().expect("stdout is piped");
+    let mut stderr_pipe = child.stderr.take().expect("stderr is piped");
+    let stdout_task = tokio::spawn(async move {
+        let mut buf = Vec::new();
+        let _ = stdout_pipe.read_to_end(&mut buf).await;
+        buf
+    });
+    let stderr_task = tokio::spawn(async move {
+        let mut buf = Vec::new();
+        let _ = stderr_pipe.read_to_end(&mut buf).await;
+        buf
+    });
+
+    let waited = tokio::select! {
+        status = child.wait() => Some(status),
+        _ = tokio::time::sleep(timeout) => None,
+    };
+    let timed_out = waited.is_none();
+    if timed_out {
+        kill_group(&mut child);
+        let _ = child.wait().await;
+    }
+    let stdout = stdout_task.await.unwrap_or_default();
+    let stderr = stderr_task.await.unwrap_or_default();
+
+    match waited {
+        Some(status) => {
+            let status = status
+                .map_err(|e| AppError::Internal(format!("failed to wait for {command}: {e}")))?;
+            Ok(std::process::Output {
+                status,
+                stdout,
+                stderr,
+            })
+        }
+        None => Err(AppError::Timeout(format!(
+            "{command} exceeded {}ms and was killed; stdout so far: {:?}; stderr so far: {:?}",
+            timeout.as_millis(),
+            String::from_utf8_lossy(&stdout),
+            String::from_utf8_lossy(&stderr)
+        ))),
+    }
+}
+
+/// How long a used idempotency key keeps deduplicating.
+const IDEMPOTENCY_TTL: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Bound on remembered keys so the map can't grow without limit.
+const IDEMPOTENCY_CAP: usize = 256;
+
+/// Recently seen `execute_plan` idempotency keys mapped to the plan id
+/// they enqueued, so a double-click or a resend after a frontend
+/// timeout returns the existing handle instead of running twice.
+#[derive(Default)]
+pub struct IdempotencyKeys {
+    seen: std::sync::Mutex<std::collections::HashMap<String, (String, std::time::Instant)>>,
+}
+
+impl IdempotencyKeys {
+    /// Return the plan id already registered under `key`, or register
+    /// `plan_id` and return `None`.
+    pub fn check_or_insert(&self, key: &str, plan_id: &str) -> Option<String> {
+        let mut seen = self.seen.lock().unwrap();
+        seen.retain(|_, (_, at)| at.elapsed() <= IDEMPOTENCY_TTL);
+        if let Some((existing, _)) = seen.get(key) {
+            return Some(existing.clone());
+        }
+        if seen.len() >= IDEMPOTENCY_CAP {
+            // Evict the oldest entry; under the TTL this only happens
+            // during a genuine burst.
+            if let Some(oldest) = seen
+                .iter()
+                .min_by_key(|(_, (_, at))| *at)
+                .map(|(k, _)| k.clone())
+            {
+                seen.remove(&oldest);
+            }
+        }
+        seen.insert(
+            key.to_string(),
+            (plan_id.to_string(), std::time::Instant::now()),
+        );
+        None
+    }
+}
+
+/// Put a confirmed plan on the serialized execution queue.
+///
+/// Plans run one at a time through the queue worker, which emits
+/// `"plan-started"` / `"plan-finished"` events; the returned plan id is
+/// how the frontend correlates them. Validation happens when the plan
+/// actually runs, so a denial surfaces in the `"plan-finished"` event
+/// and the audit log rather than here.
+///
+/// When an `idempotency_key` is supplied and a plan with the same key
+/// was already submitted recently, the existing plan id comes back and
+/// nothing is enqueued — a double-click can't deploy twice.
+#[tauri::command]
+#[tracing::instrument(skip_all, fields(plan_id = %plan.id))]
+pub async fn execute_plan(
+    plan: Plan,
+    idempotency_key: Option<String>,
+    queue: tauri::State<'_, crate::queue::ExecQueue>,
+    keys: tauri::State<'_, IdempotencyKeys>,
+) -> Result<String, AppError> {
+    if let Some(key) = &idempotency_key {
+        if let Some(existing) = keys.check_or_insert(key, &plan.id) {
+            tracing::info!(existing_plan_id = %existing, "duplicate idempotency key; not re-enqueuing");
+            return Ok(existing);
+        }
+    }
+    Ok(queue.enqueue(plan))
+}
+
+/// Run one plan to completion. Called by the queue worker only, never
+/// directly from a command.
+///
+/// The command is spawned directly (no shell) in the configured sandbox
+/// root, so the allowlist check on the binary, the metacharacter check
+/// on arguments and the path containment check are the whole policy
+/// surface. Every attempt — including denials — lands in the audit log.
+pub(crate) async fn run_plan(
+    app: &tauri::AppHandle,
+    plan: Plan,
+) -> Result<ExecutionOutcome, AppError> {
+    use tauri::Manager;
+
+    let metrics = app.state::<crate::metrics::Metrics>();
+    let result = crate::metrics::timed(
+        metrics.inner(),
+        "execute_plan",
+        execute_inner(
+            plan,
+            &app.state::<Allowlist>(),
+            &app.state::<AuditLog>(),
+            &app.state::<BackupStore>(),
+            &app.state::<SettingsStore>(),
+            &app.state::<OneShotGrants>(),
+        ),
+    )
+    .await;
+    // Keep the tray's "Recent plans" submenu in sync with the audit log.
+    crate::tray::refresh(app);
+    result
+}
+
+async fn execute_inner(
+    plan: Plan,
+    allowlist: &Allowlist,
+    audit_log: &AuditLog,
+    backups: &BackupStore,
+    settings: &SettingsStore,
+    grants: &OneShotGrants,
+) -> Result<ExecutionOutcome, AppError> {
+    let started = std::time::Instant::now();
+    let mut entry = AuditEntry {
+        timestamp: audit::now_ms(),
+        plan_id: plan.id.clone(),
+        command: plan.command.clone(),
+        args: plan.args.clone(),
+        allowed: false,
+        denied_reason: None,
+        exit_code: None,
+        stdout_bytes: 0,
+        stderr_bytes: 0,
+        duration_ms: 0,
+    };
+
+    if let Err(e) = allowlist.validate(&plan.command, &plan.args) {
+        // A one-shot grant excuses the binary check for exactly this
+        // plan id; arguments must still be metacharacter-clean.
+        let bin = plan.command.rsplit('/').next().unwrap_or(&plan.command);
+        let granted = matches!(&e, PolicyError::CommandNotAllowed(_))
+            && grants.take_if_granted(&plan.id, bin);
+        if !granted {
+            entry.denied_reason = Some(e.to_string());
+            let _ = audit_log.record(&entry);
+            return Err(e.into());
+        }
+        if let Err(e) = allowlist::validate_args(&plan.args) {
+            entry.denied_reason = Some(e.to_string());
+            let _ = audit_log.record(&entry);
+            return Err(e.into());
+        }
+        tracing::info!(binary = bin, "one-shot grant consumed");
+    }
+
+    let cfg = settings.get();
+    let sandbox_root = cfg.sandbox_root;
+    for arg in plan.args.iter().filter(|a| looks_like_path(a)) {
+        if let Err(e) = check_within_sandbox(&sandbox_root, arg) {
+            entry.denied_reason = Some(e.to_string());
+            let _ = audit_log.record(&entry);
+            return Err(e);
+        }
+    }
+    entry.allowed = true;
+
+    // The command and args are policy-checked above and carry no
+    // secrets, so logging them is safe.
+    tracing::info!(command = %plan.command, "executing plan");
+
+    // Reversible (file-touching) plans get a pre-run snapshot so
+    // undo_plan can restore them; read-only plans skip the disk cost.
+    // Relative paths in the plan resolve against the sandbox root at
+    // run time (current_dir below), so the snapshot must use the same
+    // base — otherwise backup and a later restore would act on files
+    // relative to the app's own cwd.
+    let simulation = plan::simulate(&plan, &sandbox_root);
+    if !simulation.files_touched.is_empty() {
+        let files: Vec<PathBuf> = simulation
+            .files_touched
+            .iter()
+            .map(|p| {
+                if p.is_absolute() {
+                    p.clone()
+                } else {
+                    sandbox_root.join(p)
+                }
+            })
+            .collect();
+        backups.snapshot(&plan.id, &files)?;
+    }
+
+    let result = run_with_timeout(
+        &plan.command,
+        &plan.args,
+        &sandbox_root,
+        std::time::Duration::from_millis(cfg.exec_timeout_ms),
+    )
+    .await;
+    entry.duration_ms = started.elapsed().as_millis() as u64;
+
+    let output = match result {
+        Ok(output) => output,
+        Err(e) => {
+            let _ = audit_log.record(&entry);
+            return Err(e);
+        }
+    };
+
+    entry.exit_code = output.status.code();
+    entry.stdout_bytes = output.stdout.len() as u64;
+    entry.stderr_bytes = output.stderr.len() as u64;
+    let _ = audit_log.record(&entry);
+
+    Ok(ExecutionOutcome {
+        plan_id: plan.id,
+        exit_code: output.status.code(),
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tinyllama-x-exec-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn detects_path_like_arguments() {
+        assert!(looks_like_path("/etc/passwd"));
+        assert!(looks_like_path("./notes.txt"));
+        assert!(looks_like_path("sub/dir"));
+        assert!(!looks_like_path("install"));
+        assert!(!looks_like_path("-la"));
+    }
+
+    #[test]
+    fn permits_paths_inside_root() {
+        let root = scratch_dir("inside");
+        std::fs::write(root.join("a.txt"), b"x").unwrap();
+        assert!(check_within_sandbox(&root, "./a.txt").is_ok());
+        assert!(check_within_sandbox(&root, "sub/new-file").is_ok());
+    }
+
+    #[test]
+    fn rejects_absolute_escape() {
+        let root = scratch_dir("absolute");
+        let err = check_within_sandbox(&root, "/etc/passwd").unwrap_err();
+        assert!(matches!(err, AppError::PolicyDenied(_)));
+        assert!(err.to_string().contains("/etc/passwd"));
+    }
+
+    #[test]
+    fn rejects_dotdot_escape() {
+        let root = scratch_dir("dotdot");
+        assert!(matches!(
+            check_within_sandbox(&root, "../outside"),
+            Err(AppError::PolicyDenied(_))
+        ));
+    }
+
+    #[test]
+    fn idempotency_key_returns_existing_plan_id() {
+        let keys = IdempotencyKeys::default();
+        assert_eq!(keys.check_or_insert("k1", "p1"), None);
+        assert_eq!(keys.check_or_insert("k1", "p2"), Some("p1".to_string()));
+        assert_eq!(keys.check_or_insert("k2", "p3"), None);
+    }
+
+    #[tokio::test]
+    async fn kills_runaway_command_within_window() {
+        let started = std::time::Instant::now();
+        let err = run_with_timeout(
+            "sleep",
+            &["5".to_string()],
+            &std::env::temp_dir(),
+            std::time::Duration::from_millis(200),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::Timeout(_)));
+        // Well under the 5s the command asked for: the kill worked.
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn fast_command_completes_normally() {
+        let output = run_with_timeout(
+            "echo",
+            &["ok".to_string()],
+            &std::env::temp_dir(),
+            std::time::Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ok");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlink_escape() {
+        let root = scratch_dir("symlink");
+        std::os::unix::fs::symlink("/etc", root.join("sneaky")).unwrap();
+        assert!(matches!(
+            check_within_sandbox(&root, "sneaky/passwd"),
+            Err(AppError::PolicyDenied(_))
+        ));
+    }
+}
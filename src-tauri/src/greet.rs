@@ -0,0 +1,6 @@
+//! The original scaffold command, kept for the frontend smoke test.
+
+#[tauri::command]
+pub fn greet(name: &str) -> String {
+    crate::i18n::translate("greet", &[("name", name)])
+}
@@ -0,0 +1,204 @@
+//! SQLite-backed conversation history.
+//!
+//! Every exchange survives app restarts. The connection lives behind a
+//! `Mutex` in managed state so concurrent command invocations serialize
+//! their writes instead of corrupting the database.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// One stored user/assistant exchange.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Exchange {
+    /// Unix timestamp in milliseconds.
+    pub timestamp: i64,
+    pub user_text: String,
+    pub intent: String,
+    pub plan_id: Option<String>,
+    pub outcome: Option<String>,
+}
+
+/// Managed wrapper around the history database.
+pub struct HistoryDb {
+    conn: Mutex<Connection>,
+}
+
+/// Schema migrations, applied in order based on `PRAGMA user_version`.
+const MIGRATIONS: &[&str] = &["CREATE TABLE exchanges (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        timestamp INTEGER NOT NULL,
+        user_text TEXT NOT NULL,
+        intent TEXT NOT NULL,
+        plan_id TEXT,
+        outcome TEXT
+    )"];
+
+impl HistoryDb {
+    /// Open (or create) the database under the app data directory and
+    /// bring the schema up to date.
+    pub fn open(app_data_dir: &Path) -> Result<Self, AppError> {
+        std::fs::create_dir_all(app_data_dir)
+            .map_err(|e| AppError::Storage(format!("failed to create app data dir: {e}")))?;
+        let conn = Connection::open(app_data_dir.join("history.db"))
+            .map_err(|e| AppError::Storage(format!("failed to open history db: {e}")))?;
+        Self::migrate(&conn)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn migrate(conn: &Connection) -> Result<(), AppError> {
+        let version: u32 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|e| AppError::Storage(format!("failed to read schema version: {e}")))?;
+        for (idx, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+            conn.execute_batch(migration)
+                .map_err(|e| AppError::Storage(format!("migration {idx} failed: {e}")))?;
+            conn.pragma_update(None, "user_version", idx as u32 + 1)
+                .map_err(|e| AppError::Storage(format!("failed to bump schema version: {e}")))?;
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, entry: &Exchange) -> Result<(), AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO exchanges (timestamp, user_text, intent, plan_id, outcome)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                entry.timestamp,
+                entry.user_text,
+                entry.intent,
+                entry.plan_id,
+                entry.outcome
+            ],
+        )
+        .map_err(|e| AppError::Storage(format!("failed to save exchange: {e}")))?;
+        Ok(())
+    }
+
+    pub fn list(&self, limit: u32, offset: u32) -> Result<Vec<Exchange>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                "SELECT timestamp, user_text, intent, plan_id, outcome
+                 FROM exchanges ORDER BY id DESC LIMIT ?1 OFFSET ?2",
+            )
+            .map_err(|e| AppError::Storage(format!("failed to query history: {e}")))?;
+        let rows = stmt
+            .query_map(rusqlite::params![limit, offset], |row| {
+                Ok(Exchange {
+                    timestamp: row.get(0)?,
+                    user_text: row.get(1)?,
+                    intent: row.get(2)?,
+                    plan_id: row.get(3)?,
+                    outcome: row.get(4)?,
+                })
+            })
+            .map_err(|e| AppError::Storage(format!("failed to read history rows: {e}")))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Storage(format!("failed to decode history row: {e}")))
+    }
+
+    /// Every stored exchange, oldest first, for exports.
+    pub fn all(&self) -> Result<Vec<Exchange>, AppError> {
+        let mut entries = self.list(u32::MAX, 0)?;
+        entries.reverse();
+        Ok(entries)
+    }
+
+    pub fn clear(&self) -> Result<(), AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM exchanges", [])
+            .map_err(|e| AppError::Storage(format!("failed to clear history: {e}")))?;
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub fn save_exchange(
+    entry: Exchange,
+    db: tauri::State<'_, HistoryDb>,
+) -> Result<(), AppError> {
+    db.save(&entry)
+}
+
+#[tauri::command]
+pub fn list_exchanges(
+    limit: u32,
+    offset: u32,
+    db: tauri::State<'_, HistoryDb>,
+) -> Result<Vec<Exchange>, AppError> {
+    db.list(limit, offset)
+}
+
+#[tauri::command]
+pub fn clear_history(db: tauri::State<'_, HistoryDb>) -> Result<(), AppError> {
+    db.clear()
+}
+
+/// Check the export target before writing so the user gets a precise
+/// error instead of a silent no-op.
+fn validate_export_path(path: &std::path::Path) -> Result<(), AppError> {
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty());
+    match dir {
+        Some(dir) if !dir.is_dir() => Err(AppError::InvalidInput(format!(
+            "target directory {} does not exist",
+            dir.display()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn write_export(path: &std::path::Path, contents: &str) -> Result<(), AppError> {
+    std::fs::write(path, contents).map_err(|e| {
+        AppError::Storage(format!("cannot write {}: {e}", path.display()))
+    })
+}
+
+/// Render one exchange as a Markdown section.
+fn render_markdown(entries: &[Exchange]) -> String {
+    let mut out = String::from("# TinyLlama-X session\n");
+    for entry in entries {
+        out.push_str(&format!(
+            "\n## {}\n\n**You:** {}\n\n**Intent:** `{}`\n",
+            entry.timestamp, entry.user_text, entry.intent
+        ));
+        if let Some(plan_id) = &entry.plan_id {
+            out.push_str(&format!("\n**Plan:** `{plan_id}`\n"));
+        }
+        if let Some(outcome) = &entry.outcome {
+            out.push_str(&format!("\n**Outcome:** {outcome}\n"));
+        }
+    }
+    out
+}
+
+/// Save the conversation as readable Markdown.
+#[tauri::command]
+pub fn export_history_markdown(
+    path: std::path::PathBuf,
+    db: tauri::State<'_, HistoryDb>,
+) -> Result<(), AppError> {
+    validate_export_path(&path)?;
+    let entries = db.all()?;
+    write_export(&path, &render_markdown(&entries))
+}
+
+/// Save the conversation as raw JSON (`Vec<Exchange>`).
+#[tauri::command]
+pub fn export_history_json(
+    path: std::path::PathBuf,
+    db: tauri::State<'_, HistoryDb>,
+) -> Result<(), AppError> {
+    validate_export_path(&path)?;
+    let entries = db.all()?;
+    let json = serde_json::to_string_pretty(&entries)
+        .map_err(|e| AppError::Internal(format!("failed to encode history: {e}")))?;
+    write_export(&path, &json)
+}
@@ -0,0 +1,104 @@
+//! Locale lookup for user-facing strings.
+//!
+//! Locale tables are bundled JSON (`locales/*.json`) compiled into the
+//! binary. The active locale lives in a module-level static rather than
+//! managed state so error serialization — which has no access to the
+//! Tauri app handle — can localize messages too. Missing keys fall back
+//! to English rather than leaking a raw key to the UI.
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use crate::error::AppError;
+
+/// Fallback locale; every key must exist here.
+const DEFAULT_LOCALE: &str = "en";
+
+fn tables() -> &'static HashMap<&'static str, HashMap<String, String>> {
+    static TABLES: OnceLock<HashMap<&'static str, HashMap<String, String>>> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut tables = HashMap::new();
+        for (tag, raw) in [
+            ("en", include_str!("../locales/en.json")),
+            ("es", include_str!("../locales/es.json")),
+        ] {
+            let table: HashMap<String, String> = serde_json::from_str(raw)
+                .unwrap_or_else(|e| panic!("bundled locale {tag} is invalid JSON: {e}"));
+            tables.insert(tag, table);
+        }
+        tables
+    })
+}
+
+fn active() -> &'static RwLock<String> {
+    static ACTIVE: OnceLock<RwLock<String>> = OnceLock::new();
+    ACTIVE.get_or_init(|| RwLock::new(DEFAULT_LOCALE.to_string()))
+}
+
+/// Replace `{param}` slots in a locale string. Unknown slots are left
+/// in place; locale files are bundled, so that only happens during
+/// development.
+fn interpolate(template: &str, params: &[(&str, &str)]) -> String {
+    let mut out = template.to_string();
+    for (key, value) in params {
+        out = out.replace(&format!("{{{key}}}"), value);
+    }
+    out
+}
+
+/// Look up `key` in the active locale with `{param}` interpolation,
+/// falling back to English when the key is missing.
+pub fn translate(key: &str, params: &[(&str, &str)]) -> String {
+    let tag = active().read().unwrap().clone();
+    let tables = tables();
+    let entry = tables
+        .get(tag.as_str())
+        .and_then(|t| t.get(key))
+        .or_else(|| tables.get(DEFAULT_LOCALE).and_then(|t| t.get(key)));
+    match entry {
+        Some(template) => interpolate(template, params),
+        None => key.to_string(),
+    }
+}
+
+/// Localized message for an error code, with the code-specific detail
+/// text spliced into the `{detail}` slot.
+pub fn error_message(code: &str, detail: &str) -> String {
+    translate(&format!("error.{code}"), &[("detail", detail)])
+}
+
+/// Switch the active locale.
+#[tauri::command]
+pub fn set_locale(tag: String) -> Result<(), AppError> {
+    if !tables().contains_key(tag.as_str()) {
+        return Err(AppError::InvalidInput(format!(
+            "unknown locale {tag:?}; bundled locales: en, es"
+        )));
+    }
+    *active().write().unwrap() = tag;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_greeting() {
+        let msg = translate("greet", &[("name", "Ada")]);
+        assert!(msg.contains("Ada"));
+    }
+
+    #[test]
+    fn missing_key_falls_back_to_english_then_key() {
+        assert_eq!(translate("no.such.key", &[]), "no.such.key");
+    }
+
+    #[test]
+    fn rejects_unknown_locale() {
+        assert!(matches!(
+            set_locale("tlh".into()),
+            Err(AppError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,76 @@
+//! User-input validation shared by every backend-bound command.
+//!
+//! One place decides what text is allowed to reach the backend: no
+//! empty or whitespace-only strings, a configurable length cap, and no
+//! control characters (newlines and tabs excepted — they're normal in
+//! pasted text). Callers use the returned normalized string so the
+//! cleaned version is what actually gets sent.
+
+use crate::error::AppError;
+
+/// Default cap, used when settings are not consulted.
+pub const DEFAULT_MAX_INPUT_CHARS: usize = 8192;
+
+/// Validate and normalize user text before it goes anywhere.
+///
+/// Trims surrounding whitespace, rejects empty input and input over
+/// `max_chars`, and strips control characters other than `\n` / `\t`.
+pub fn validate_user_input(text: &str, max_chars: usize) -> Result<String, AppError> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return Err(AppError::InvalidInput("input is empty".into()));
+    }
+    let count = trimmed.chars().count();
+    if count > max_chars {
+        return Err(AppError::InvalidInput(format!(
+            "input is {count} characters; the limit is {max_chars}"
+        )));
+    }
+    Ok(trimmed
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\t'))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_and_passes_normal_input() {
+        assert_eq!(
+            validate_user_input("  install htop  ", DEFAULT_MAX_INPUT_CHARS).unwrap(),
+            "install htop"
+        );
+    }
+
+    #[test]
+    fn rejects_empty_and_whitespace_only() {
+        assert!(matches!(
+            validate_user_input("", DEFAULT_MAX_INPUT_CHARS),
+            Err(AppError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            validate_user_input("   \n\t ", DEFAULT_MAX_INPUT_CHARS),
+            Err(AppError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_over_limit() {
+        let long = "x".repeat(10);
+        assert!(matches!(
+            validate_user_input(&long, 9),
+            Err(AppError::InvalidInput(_))
+        ));
+        assert!(validate_user_input(&long, 10).is_ok());
+    }
+
+    #[test]
+    fn strips_control_characters_but_keeps_newlines() {
+        assert_eq!(
+            validate_user_input("a\u{0007}b\nc\td", DEFAULT_MAX_INPUT_CHARS).unwrap(),
+            "ab\nc\td"
+        );
+    }
+}
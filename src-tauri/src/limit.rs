@@ -0,0 +1,107 @@
+//! Global concurrency limit for backend-bound commands.
+//!
+//! A local model degrades badly under parallel load, so at most N
+//! requests (configurable, default 4) talk to the backend at once.
+//! Excess callers wait on the semaphore; if no slot frees up within
+//! the busy threshold they get [`AppError::Busy`] instead of hanging
+//! indefinitely. The in-flight count is surfaced through the metrics
+//! snapshot.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::error::AppError;
+
+/// How long a caller may wait for a slot before getting `Busy`.
+const BUSY_AFTER: Duration = Duration::from_secs(5);
+
+/// Managed gate shared by every backend-bound command.
+pub struct BackendGate {
+    semaphore: Arc<Semaphore>,
+    in_flight: Arc<AtomicUsize>,
+    permits: usize,
+    busy_after: Duration,
+}
+
+/// Releases the slot (and decrements the in-flight count) on drop.
+pub struct GatePermit {
+    _permit: OwnedSemaphorePermit,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for GatePermit {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl BackendGate {
+    pub fn new(permits: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(permits)),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            permits,
+            busy_after: BUSY_AFTER,
+        }
+    }
+
+    /// Requests currently holding a slot.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Wait for a slot, failing with `Busy` once the threshold passes.
+    pub async fn acquire(&self) -> Result<GatePermit, AppError> {
+        match tokio::time::timeout(self.busy_after, self.semaphore.clone().acquire_owned()).await
+        {
+            Ok(Ok(permit)) => {
+                self.in_flight.fetch_add(1, Ordering::SeqCst);
+                Ok(GatePermit {
+                    _permit: permit,
+                    in_flight: self.in_flight.clone(),
+                })
+            }
+            Ok(Err(_)) => Err(AppError::Internal("backend gate closed".into())),
+            Err(_) => Err(AppError::Busy(format!(
+                "all {} backend slots stayed busy for {}s",
+                self.permits,
+                self.busy_after.as_secs()
+            ))),
+        }
+    }
+}
+
+impl Default for BackendGate {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tracks_in_flight_count() {
+        let gate = BackendGate::new(2);
+        assert_eq!(gate.in_flight(), 0);
+        let a = gate.acquire().await.unwrap();
+        let b = gate.acquire().await.unwrap();
+        assert_eq!(gate.in_flight(), 2);
+        drop(a);
+        assert_eq!(gate.in_flight(), 1);
+        drop(b);
+        assert_eq!(gate.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn returns_busy_when_no_slot_frees_up() {
+        let mut gate = BackendGate::new(1);
+        gate.busy_after = Duration::from_millis(50);
+        let _held = gate.acquire().await.unwrap();
+        assert!(matches!(gate.acquire().await, Err(AppError::Busy(_))));
+    }
+}
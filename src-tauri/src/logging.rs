@@ -0,0 +1,63 @@
+//! Structured tracing with a rolling file under `app_data_dir/logs/`.
+//!
+//! Commands carry spans with a `request_id` field, so one request's
+//! lines can be grep'd out of the file. The level filter is behind a
+//! reload handle so `set_log_level` can bump verbosity at runtime while
+//! a user reproduces an issue.
+//!
+//! Secrets never reach the log: command spans use `skip_all` so
+//! payloads aren't captured, and `secrets::set_api_key` is deliberately
+//! not instrumented at all.
+
+use std::path::Path;
+
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::error::AppError;
+
+/// Managed handle to the live filter, plus the appender guard that
+/// flushes buffered lines on drop.
+pub struct LogState {
+    handle: reload::Handle<EnvFilter, Registry>,
+    _guard: tracing_appender::non_blocking::WorkerGuard,
+}
+
+/// Install the global subscriber writing to a daily-rolling file.
+pub fn init(app_data_dir: &Path) -> Result<LogState, AppError> {
+    let dir = app_data_dir.join("logs");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| AppError::Storage(format!("failed to create log dir: {e}")))?;
+    let appender = tracing_appender::rolling::daily(&dir, "tinyllama-x.log");
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let (filter, handle) = reload::Layer::new(filter);
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(writer)
+                .with_ansi(false),
+        )
+        .init();
+
+    Ok(LogState {
+        handle,
+        _guard: guard,
+    })
+}
+
+/// Change the log level at runtime, e.g. `"debug"` or a full filter
+/// directive like `"tinyllama_x=trace"`.
+#[tauri::command]
+pub fn set_log_level(level: String, logs: tauri::State<'_, LogState>) -> Result<(), AppError> {
+    let filter = EnvFilter::try_new(&level)
+        .map_err(|e| AppError::InvalidInput(format!("invalid log filter {level:?}: {e}")))?;
+    logs.handle
+        .reload(filter)
+        .map_err(|e| AppError::Internal(format!("failed to reload log filter: {e}")))?;
+    tracing::info!(%level, "log level changed");
+    Ok(())
+}
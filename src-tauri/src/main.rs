@@ -0,0 +1,132 @@
+// Prevents additional console window on Windows in release, DO NOT REMOVE!!
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+mod allowlist;
+mod audit;
+mod bridge;
+mod cache;
+mod cancel;
+mod clipboard;
+mod compat;
+mod context;
+mod deeplink;
+mod diff;
+mod document;
+mod error;
+mod exec;
+mod greet;
+mod history;
+mod i18n;
+mod input;
+mod limit;
+mod logging;
+mod metrics;
+mod models;
+#[cfg(feature = "pyo3")]
+mod native;
+mod notify;
+mod offline;
+mod plan;
+mod plugins;
+mod prompt;
+mod queue;
+mod resources;
+mod rollback;
+mod secrets;
+mod session;
+mod settings;
+mod shutdown;
+mod sidecar;
+mod slots;
+mod status;
+mod stream;
+mod templates;
+mod tray;
+mod ws;
+
+// TODO: backend wiring options under evaluation
+//   1. HTTP bridge to the FastAPI presenter (implemented in bridge.rs)
+//   2. direct execution behind a strict allowlist (allowlist.rs / exec.rs)
+//   3. native in-process PyO3 binding to the presenter module
+
+fn main() {
+    let builder = tauri::Builder::default()
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
+        .manage(clipboard::LastResult::default())
+        .manage(notify::PendingNotification::default())
+        .manage(allowlist::Allowlist::default())
+        .manage(allowlist::OneShotGrants::default())
+        .manage(exec::IdempotencyKeys::default())
+        .manage(cache::IntentCache::default())
+        .manage(cancel::CancelRegistry::default())
+        .manage(metrics::Metrics::default())
+        .manage(models::ModelState::default())
+        .manage(queue::ExecQueue::default())
+        .manage(offline::OnlineState::default())
+        .manage(resources::ResourceMonitor::default())
+        .manage(sidecar::SidecarState::default())
+        .manage(ws::WsBridge::default())
+        .setup(|app| {
+            use tauri::Manager;
+            let data_dir = app.path().app_data_dir()?;
+            app.manage(logging::init(&data_dir)?);
+            // Settings seed the bridge config, so the bridge is built
+            // here rather than managed up front.
+            let settings_store = settings::SettingsStore::open(&data_dir)?;
+            let status_port = settings_store.get().status_port;
+            app.manage(bridge::Bridge::new(settings_store.get().bridge));
+            app.manage(limit::BackendGate::new(
+                settings_store.get().max_concurrent_requests,
+            ));
+            app.manage(settings_store);
+            // Pick up a previously stored API key without exposing it.
+            if let Ok(Some(key)) = secrets::load() {
+                app.state::<bridge::Bridge>().set_auth_token(Some(key));
+            }
+            app.manage(history::HistoryDb::open(&data_dir)?);
+            app.manage(audit::AuditLog::open(&data_dir)?);
+            app.manage(templates::TemplateStore::open(&data_dir)?);
+            app.manage(prompt::PromptStore::open(&data_dir)?);
+            app.manage(slots::SlotSchemaStore::open(&data_dir)?);
+            app.manage(rollback::BackupStore::open(&data_dir)?);
+            tray::init(app.handle())?;
+            deeplink::init(app.handle())?;
+            queue::spawn_worker(app.handle().clone());
+            // Opt-in external monitoring endpoint; a taken port fails
+            // startup loudly rather than leaving a dead endpoint.
+            if status_port != 0 {
+                app.manage(status::start(app.handle(), status_port)?);
+            }
+            // Warn early if the backend is already up but too old/new.
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                compat::check_and_warn(&handle).await;
+            });
+            Ok(())
+        });
+
+    let app = plugins::register(builder)
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application");
+
+    app.run(|app_handle, event| match event {
+        tauri::RunEvent::ExitRequested { .. } => {
+            shutdown::run(app_handle);
+        }
+        tauri::RunEvent::Exit => {
+            // Backstop for exits that skip ExitRequested; the teardown
+            // itself only runs once.
+            shutdown::run(app_handle);
+        }
+        tauri::RunEvent::WindowEvent {
+            event: tauri::WindowEvent::Focused(true),
+            ..
+        } => {
+            notify::on_window_focused(app_handle);
+        }
+        _ => {}
+    });
+}
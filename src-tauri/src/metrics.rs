@@ -0,0 +1,170 @@
+//! Lightweight in-memory metrics for bridge and command performance.
+//!
+//! Commands wrap their bodies in [`timed`] so call counts, error counts
+//! and latency percentiles accumulate without hand-instrumenting each
+//! call site. Everything lives in memory; `reset_metrics` starts a
+//! fresh benchmarking window.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// Latency samples kept per command; old samples are dropped FIFO.
+const MAX_SAMPLES: usize = 1024;
+
+#[derive(Default)]
+struct CommandStats {
+    calls: u64,
+    errors: u64,
+    latencies_ms: Vec<u64>,
+}
+
+/// Managed metrics store.
+#[derive(Default)]
+pub struct Metrics {
+    commands: Mutex<HashMap<String, CommandStats>>,
+}
+
+/// Per-command numbers in a snapshot.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandMetrics {
+    pub command: String,
+    pub calls: u64,
+    pub errors: u64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+}
+
+/// Point-in-time view handed to the frontend.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct MetricsSnapshot {
+    pub commands: Vec<CommandMetrics>,
+    /// Backend-bound requests currently holding a concurrency slot.
+    pub in_flight: usize,
+}
+
+/// Nearest-rank percentile: the smallest sample such that `pct` of the
+/// set is at or below it.
+fn percentile(sorted: &[u64], pct: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (pct * sorted.len() as f64).ceil() as usize;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+impl Metrics {
+    /// Record one completed invocation.
+    pub fn observe(&self, command: &str, duration_ms: u64, ok: bool) {
+        let mut commands = self.commands.lock().unwrap();
+        let stats = commands.entry(command.to_string()).or_default();
+        stats.calls += 1;
+        if !ok {
+            stats.errors += 1;
+        }
+        if stats.latencies_ms.len() >= MAX_SAMPLES {
+            stats.latencies_ms.remove(0);
+        }
+        stats.latencies_ms.push(duration_ms);
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let commands = self.commands.lock().unwrap();
+        let mut out: Vec<CommandMetrics> = commands
+            .iter()
+            .map(|(name, stats)| {
+                let mut sorted = stats.latencies_ms.clone();
+                sorted.sort_unstable();
+                CommandMetrics {
+                    command: name.clone(),
+                    calls: stats.calls,
+                    errors: stats.errors,
+                    p50_ms: percentile(&sorted, 0.50),
+                    p95_ms: percentile(&sorted, 0.95),
+                }
+            })
+            .collect();
+        out.sort_by(|a, b| a.command.cmp(&b.command));
+        MetricsSnapshot {
+            commands: out,
+            in_flight: 0,
+        }
+    }
+
+    pub fn reset(&self) {
+        self.commands.lock().unwrap().clear();
+    }
+}
+
+/// Run a command body while recording its duration and outcome.
+pub async fn timed<T, F>(metrics: &Metrics, command: &str, work: F) -> Result<T, AppError>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    let started = std::time::Instant::now();
+    let result = work.await;
+    metrics.observe(
+        command,
+        started.elapsed().as_millis() as u64,
+        result.is_ok(),
+    );
+    result
+}
+
+/// Current per-command call/error counts and latency percentiles,
+/// plus the live in-flight request count.
+#[tauri::command]
+pub fn get_metrics(
+    metrics: tauri::State<'_, Metrics>,
+    gate: tauri::State<'_, crate::limit::BackendGate>,
+) -> MetricsSnapshot {
+    let mut snapshot = metrics.snapshot();
+    snapshot.in_flight = gate.in_flight();
+    snapshot
+}
+
+/// Clear all recorded metrics, e.g. between benchmarking runs.
+#[tauri::command]
+pub fn reset_metrics(metrics: tauri::State<'_, Metrics>) -> Result<(), AppError> {
+    metrics.reset();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_on_empty_are_zero() {
+        assert_eq!(percentile(&[], 0.95), 0);
+    }
+
+    #[test]
+    fn observe_accumulates_counts_and_percentiles() {
+        let metrics = Metrics::default();
+        for ms in [10, 20, 30, 40, 100] {
+            metrics.observe("classify_intent", ms, true);
+        }
+        metrics.observe("classify_intent", 500, false);
+
+        let snapshot = metrics.snapshot();
+        let stats = &snapshot.commands[0];
+        assert_eq!(stats.command, "classify_intent");
+        assert_eq!(stats.calls, 6);
+        assert_eq!(stats.errors, 1);
+        assert_eq!(stats.p50_ms, 30);
+        assert_eq!(stats.p95_ms, 500);
+    }
+
+    #[test]
+    fn reset_clears_everything() {
+        let metrics = Metrics::default();
+        metrics.observe("greet", 1, true);
+        metrics.reset();
+        assert!(metrics.snapshot().commands.is_empty());
+    }
+}
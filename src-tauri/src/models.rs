@@ -0,0 +1,82 @@
+//! Model discovery and selection.
+//!
+//! The backend may host several TinyLlama variants; the active model id
+//! lives in managed state and is attached to every downstream request.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bridge::Bridge;
+use crate::error::AppError;
+
+/// One model as reported by the backend's `/models` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelInfo {
+    pub id: String,
+    pub name: String,
+    pub context_length: u32,
+    pub quantization: String,
+}
+
+/// Managed state: the currently selected model plus the last list we
+/// fetched, used to validate selections.
+#[derive(Default)]
+pub struct ModelState {
+    inner: Mutex<ModelStateInner>,
+}
+
+#[derive(Default)]
+struct ModelStateInner {
+    active: Option<String>,
+    known: Vec<ModelInfo>,
+}
+
+impl ModelState {
+    /// Id of the model subsequent classify/generate calls should use.
+    pub fn active(&self) -> Option<String> {
+        self.inner.lock().unwrap().active.clone()
+    }
+
+    fn remember(&self, models: &[ModelInfo]) {
+        self.inner.lock().unwrap().known = models.to_vec();
+    }
+
+    fn select(&self, model_id: &str) -> Result<(), AppError> {
+        let mut inner = self.inner.lock().unwrap();
+        if !inner.known.iter().any(|m| m.id == model_id) {
+            return Err(AppError::InvalidInput(format!(
+                "unknown model id {model_id:?}; call list_models first"
+            )));
+        }
+        inner.active = Some(model_id.to_string());
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelsResponse {
+    models: Vec<ModelInfo>,
+}
+
+/// Fetch the models available on the backend.
+#[tauri::command]
+pub async fn list_models(
+    bridge: tauri::State<'_, Bridge>,
+    state: tauri::State<'_, ModelState>,
+) -> Result<Vec<ModelInfo>, AppError> {
+    let body: ModelsResponse = bridge.get_idempotent("/models").await?;
+    state.remember(&body.models);
+    Ok(body.models)
+}
+
+/// Select the model used for subsequent classify/generate calls.
+///
+/// Fails if the id wasn't present in the last fetched list.
+#[tauri::command]
+pub fn set_active_model(
+    model_id: String,
+    state: tauri::State<'_, ModelState>,
+) -> Result<(), AppError> {
+    state.select(&model_id)
+}
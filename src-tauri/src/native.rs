@@ -0,0 +1,68 @@
+//! In-process PyO3 binding to the TinyLlama-X presenter (TODO option 3).
+//!
+//! Compiled only with the `pyo3` cargo feature so the default build
+//! stays lightweight and needs no embedded interpreter. The Python calls
+//! run on the blocking thread pool because they hold the GIL for the
+//! duration of the call; keeping them off the async runtime keeps the
+//! UI responsive.
+//!
+//! Scope: only classification goes in-process. Simulation stays in
+//! Rust even under this feature — the structured preview (steps, risk,
+//! predicted diffs) is built here, while the Python planner renders
+//! flat preview text that the confirmation screen can't use.
+
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::bridge::IntentResult;
+use crate::error::AppError;
+
+fn py_err(e: PyErr) -> AppError {
+    AppError::Internal(format!("python backend error: {e}"))
+}
+
+/// Classify `text` by importing `lib.intent` and calling
+/// `classify_intent` directly, no HTTP round-trip involved.
+pub async fn classify(text: String) -> Result<IntentResult, AppError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        Python::with_gil(|py| {
+            let module = py.import("lib.intent").map_err(py_err)?;
+            let intent = module
+                .getattr("classify_intent")
+                .map_err(py_err)?
+                .call1((text,))
+                .map_err(py_err)?;
+
+            let intent_name: String = intent
+                .getattr("type")
+                .and_then(|t| t.getattr("value"))
+                .and_then(|v| v.extract())
+                .map_err(py_err)?;
+            let confidence: f32 = intent
+                .getattr("confidence")
+                .and_then(|c| c.extract())
+                .map_err(py_err)?;
+
+            let mut slots = HashMap::new();
+            let entities = intent
+                .getattr("entities")
+                .map_err(py_err)?
+                .downcast::<PyDict>()
+                .map_err(|e| AppError::Internal(format!("python backend error: {e}")))?;
+            for (key, value) in entities.iter() {
+                slots.insert(key.to_string(), value.to_string());
+            }
+
+            Ok(IntentResult {
+                intent: intent_name,
+                confidence,
+                slots,
+                cached: false,
+            })
+        })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("python worker panicked: {e}")))?
+}
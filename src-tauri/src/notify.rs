@@ -0,0 +1,58 @@
+//! Desktop notifications for plans that finish in the background.
+//!
+//! Fired from the queue worker when a plan completes while the window
+//! is unfocused, gated by the `notify_on_completion` setting. Desktop
+//! platforms don't give us a reliable per-notification click callback,
+//! so activation is approximated: the next time the window gains focus
+//! after a notification, a `"notification-activated"` event carries the
+//! plan id so the UI can scroll to that plan.
+
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::settings::SettingsStore;
+
+/// Plan id of the most recent notification, consumed on next focus.
+#[derive(Default)]
+pub struct PendingNotification(Mutex<Option<String>>);
+
+/// Show an OS notification for a finished plan, unless the user turned
+/// them off or is already looking at the window.
+pub fn notify_plan_finished(app: &AppHandle, plan_id: &str, description: &str, success: bool) {
+    if !app.state::<SettingsStore>().get().notify_on_completion {
+        return;
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        if window.is_focused().unwrap_or(false) {
+            return;
+        }
+    }
+    let title = if success {
+        "Plan completed"
+    } else {
+        "Plan failed"
+    };
+    if app
+        .notification()
+        .builder()
+        .title(title)
+        .body(description)
+        .show()
+        .is_ok()
+    {
+        *app.state::<PendingNotification>().0.lock().unwrap() = Some(plan_id.to_string());
+    }
+}
+
+/// Called when the main window gains focus; resolves a pending
+/// notification into a `"notification-activated"` event.
+pub fn on_window_focused(app: &AppHandle) {
+    let Some(state) = app.try_state::<PendingNotification>() else {
+        return;
+    };
+    if let Some(plan_id) = state.0.lock().unwrap().take() {
+        let _ = app.emit("notification-activated", plan_id);
+    }
+}
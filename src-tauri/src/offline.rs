@@ -0,0 +1,74 @@
+//! Offline detection with a failure cooldown.
+//!
+//! After the backend is seen unreachable, backend-bound commands
+//! short-circuit with `Offline` for a cooldown window instead of each
+//! waiting out its own connection timeout. Once the window elapses the
+//! next user action probes again.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::bridge::Bridge;
+use crate::error::AppError;
+
+/// How long to assume the backend is still down after a failure.
+const COOLDOWN: Duration = Duration::from_secs(10);
+
+/// Managed reachability state.
+#[derive(Default)]
+pub struct OnlineState {
+    last_failure: Mutex<Option<Instant>>,
+}
+
+impl OnlineState {
+    /// Fail fast while inside the cooldown window after a failure.
+    pub fn guard(&self) -> Result<(), AppError> {
+        if let Some(failed_at) = *self.last_failure.lock().unwrap() {
+            let remaining = COOLDOWN.saturating_sub(failed_at.elapsed());
+            if !remaining.is_zero() {
+                return Err(AppError::Offline(format!(
+                    "backend unreachable; retrying in {}s",
+                    remaining.as_secs().max(1)
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn note_success(&self) {
+        *self.last_failure.lock().unwrap() = None;
+    }
+
+    pub fn note_failure(&self) {
+        *self.last_failure.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Update the state from a command outcome. Only transport-level
+    /// failures count; an upstream 4xx means the backend is fine.
+    pub fn observe<T>(&self, result: &Result<T, AppError>) {
+        match result {
+            Ok(_) => self.note_success(),
+            Err(
+                AppError::BackendUnreachable(_)
+                | AppError::Timeout(_)
+                | AppError::RetriesExhausted { .. },
+            ) => self.note_failure(),
+            Err(_) => {}
+        }
+    }
+}
+
+/// Actively probe the backend and report reachability.
+#[tauri::command]
+pub async fn is_online(
+    bridge: tauri::State<'_, Bridge>,
+    online: tauri::State<'_, OnlineState>,
+) -> Result<bool, AppError> {
+    let reachable = bridge.health().await.reachable;
+    if reachable {
+        online.note_success();
+    } else {
+        online.note_failure();
+    }
+    Ok(reachable)
+}
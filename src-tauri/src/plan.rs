@@ -0,0 +1,180 @@
+//! Plan types shared by the simulate and execute paths.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// A resolved plan produced by the presenter: a single command plus the
+/// metadata the UI shows for confirmation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Plan {
+    pub id: String,
+    pub description: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// One step of a simulated plan, for the confirmation screen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanStep {
+    pub command: String,
+    pub args: Vec<String>,
+    pub description: String,
+}
+
+/// Coarse risk classification shown before the user confirms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+/// Structured preview of what a plan would do, without executing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationResult {
+    pub steps: Vec<PlanStep>,
+    pub files_touched: Vec<PathBuf>,
+    pub estimated_risk: RiskLevel,
+    /// Predicted per-file diffs, where the command's outcome is
+    /// predictable; empty otherwise.
+    #[serde(default)]
+    pub diffs: Vec<crate::diff::FileDiff>,
+}
+
+/// Binaries that run with elevated impact: package managers and service
+/// control. Anything here is High risk regardless of arguments.
+const PRIVILEGED_BINS: &[&str] = &[
+    "sudo", "apt", "apt-get", "dnf", "pacman", "zypper", "apk", "systemctl",
+];
+
+/// Binaries that write to the filesystem.
+const FILE_WRITING_BINS: &[&str] = &["cp", "mv", "rm", "mkdir", "touch", "chmod", "chown", "tee"];
+
+/// Binaries that reach the network.
+const NETWORK_BINS: &[&str] = &["curl", "wget", "ssh", "scp", "nc"];
+
+fn basename(cmd: &str) -> &str {
+    cmd.rsplit('/').next().unwrap_or(cmd)
+}
+
+/// Estimate how risky a plan is from the binary it runs.
+pub fn estimate_risk(plan: &Plan) -> RiskLevel {
+    let bin = basename(&plan.command);
+    if PRIVILEGED_BINS.contains(&bin) {
+        RiskLevel::High
+    } else if FILE_WRITING_BINS.contains(&bin) || NETWORK_BINS.contains(&bin) {
+        RiskLevel::Medium
+    } else {
+        RiskLevel::Low
+    }
+}
+
+/// Arguments that look like filesystem paths the plan would touch.
+fn files_touched(plan: &Plan) -> Vec<PathBuf> {
+    if !FILE_WRITING_BINS.contains(&basename(&plan.command)) {
+        return Vec::new();
+    }
+    plan.args
+        .iter()
+        .filter(|a| !a.starts_with('-'))
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Build the structured preview for a plan. `sandbox_root` is where
+/// the plan will run, so predicted diffs read relative paths from the
+/// same place execution will touch them.
+pub fn simulate(plan: &Plan, sandbox_root: &std::path::Path) -> SimulationResult {
+    SimulationResult {
+        steps: vec![PlanStep {
+            command: plan.command.clone(),
+            args: plan.args.clone(),
+            description: plan.description.clone(),
+        }],
+        files_touched: files_touched(plan),
+        estimated_risk: estimate_risk(plan),
+        diffs: crate::diff::predicted_diffs(plan, sandbox_root),
+    }
+}
+
+/// Payload for `"simulation-progress"` events.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationProgress {
+    pub plan_id: String,
+    /// 1-based step index.
+    pub step: u32,
+    pub total: u32,
+    pub description: String,
+}
+
+/// Preview what a plan would do so the UI can show a confirmation
+/// screen before anything runs.
+///
+/// Emits one `"simulation-progress"` event per evaluated step so long
+/// simulations can drive a determinate progress bar. [`simulate`]
+/// always derives at least one step, so there is no indeterminate
+/// state to signal.
+#[tauri::command]
+pub async fn simulate_plan(
+    plan: Plan,
+    window: tauri::Window,
+    settings: tauri::State<'_, crate::settings::SettingsStore>,
+) -> Result<SimulationResult, AppError> {
+    use tauri::Emitter;
+
+    let result = simulate(&plan, &settings.get().sandbox_root);
+    let total = result.steps.len() as u32;
+    for (idx, step) in result.steps.iter().enumerate() {
+        let _ = window.emit(
+            "simulation-progress",
+            SimulationProgress {
+                plan_id: plan.id.clone(),
+                step: idx as u32 + 1,
+                total,
+                description: step.description.clone(),
+            },
+        );
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(command: &str, args: &[&str]) -> Plan {
+        Plan {
+            id: "p1".into(),
+            description: "test plan".into(),
+            command: command.into(),
+            args: args.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn package_manager_is_high_risk() {
+        assert_eq!(estimate_risk(&plan("apt", &["install", "htop"])), RiskLevel::High);
+    }
+
+    #[test]
+    fn file_writer_is_medium_risk_and_reports_paths() {
+        let result = simulate(&plan("rm", &["-f", "/tmp/a", "/tmp/b"]), std::path::Path::new("/"));
+        assert_eq!(result.estimated_risk, RiskLevel::Medium);
+        assert_eq!(
+            result.files_touched,
+            vec![PathBuf::from("/tmp/a"), PathBuf::from("/tmp/b")]
+        );
+    }
+
+    #[test]
+    fn read_only_command_is_low_risk() {
+        let result = simulate(&plan("ls", &["-la"]), std::path::Path::new("/"));
+        assert_eq!(result.estimated_risk, RiskLevel::Low);
+        assert!(result.files_touched.is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+//! Command registry.
+//!
+//! Each module exposes its own `#[tauri::command]` handlers; this is
+//! the single place they are composed into the builder, so `main.rs`
+//! stays a thin bootstrap and adding a command never touches it. New
+//! modules register here.
+
+use tauri::{Builder, Runtime};
+
+/// Attach every command handler to the builder.
+pub fn register<R: Runtime>(builder: Builder<R>) -> Builder<R> {
+    builder.invoke_handler(tauri::generate_handler![
+        crate::greet::greet,
+        crate::i18n::set_locale,
+        crate::logging::set_log_level,
+        crate::bridge::classify_intent,
+        crate::bridge::classify_batch,
+        crate::context::classify_with_context,
+        crate::document::classify_document,
+        crate::slots::validate_slots,
+        crate::bridge::backend_health,
+        crate::compat::check_compatibility,
+        crate::bridge::get_active_endpoint,
+        crate::bridge::device_info,
+        crate::stream::generate_stream,
+        crate::prompt::list_prompt_templates,
+        crate::prompt::set_prompt_template,
+        crate::cancel::cancel_request,
+        crate::cache::clear_cache,
+        crate::offline::is_online,
+        crate::models::list_models,
+        crate::models::set_active_model,
+        crate::exec::execute_plan,
+        crate::queue::list_queue,
+        crate::queue::dequeue_plan,
+        crate::allowlist::grant_once,
+        crate::rollback::undo_plan,
+        crate::plan::simulate_plan,
+        crate::history::save_exchange,
+        crate::history::list_exchanges,
+        crate::history::clear_history,
+        crate::history::export_history_markdown,
+        crate::history::export_history_json,
+        crate::audit::read_audit,
+        crate::templates::save_template,
+        crate::templates::list_templates,
+        crate::templates::instantiate_template,
+        crate::clipboard::copy_to_clipboard,
+        crate::clipboard::copy_last_result,
+        crate::resources::resource_usage,
+        crate::metrics::get_metrics,
+        crate::metrics::reset_metrics,
+        crate::sidecar::start_backend,
+        crate::sidecar::stop_backend,
+        crate::secrets::set_api_key,
+        crate::secrets::has_api_key,
+        crate::settings::get_settings,
+        crate::settings::update_settings,
+        crate::session::export_session,
+        crate::session::import_session
+    ])
+}
@@ -0,0 +1,238 @@
+//! Prompt templates for the generate path.
+//!
+//! The frontend used to send raw prompts straight to `/generate`; now
+//! the Rust layer wraps the user's text into the active
+//! [`PromptTemplate`] first, so system instructions live in config
+//! rather than in frontend code. Templates are loaded from
+//! `app_data_dir/prompts.json` (defaults are written on first launch,
+//! same as settings). The user's text is spliced in as data at the
+//! literal `{input}` marker — it is never scanned for template syntax,
+//! so input containing `{input}` or `{anything}` cannot alter the
+//! template.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// Marker in a template's user section where the input goes.
+const PLACEHOLDER: &str = "{input}";
+
+/// One prompt template: an optional system section sent alongside the
+/// prompt, and a user section containing the `{input}` marker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub name: String,
+    /// System instructions; empty means none are sent.
+    #[serde(default)]
+    pub system: String,
+    /// User section; must contain `{input}` at least once.
+    pub user: String,
+}
+
+/// On-disk shape of `prompts.json`.
+#[derive(Debug, Serialize, Deserialize)]
+struct PromptsFile {
+    active: String,
+    templates: Vec<PromptTemplate>,
+}
+
+fn default_templates() -> PromptsFile {
+    PromptsFile {
+        active: "plain".into(),
+        templates: vec![
+            PromptTemplate {
+                name: "plain".into(),
+                system: String::new(),
+                user: PLACEHOLDER.into(),
+            },
+            PromptTemplate {
+                name: "assistant".into(),
+                system: "You are TinyLlama-X, a concise terminal assistant. \
+                         Answer briefly and prefer shell-ready output."
+                    .into(),
+                user: PLACEHOLDER.into(),
+            },
+        ],
+    }
+}
+
+/// The two strings actually sent to the backend for one generation.
+#[derive(Debug, Clone)]
+pub struct RenderedPrompt {
+    pub system: Option<String>,
+    pub prompt: String,
+}
+
+/// Managed prompt-template storage.
+pub struct PromptStore {
+    path: PathBuf,
+    file: Mutex<PromptsFile>,
+}
+
+fn validate_file(file: &PromptsFile) -> Result<(), AppError> {
+    if file.templates.is_empty() {
+        return Err(AppError::Storage(
+            "prompts.json defines no templates".into(),
+        ));
+    }
+    for template in &file.templates {
+        if !template.user.contains(PLACEHOLDER) {
+            return Err(AppError::Storage(format!(
+                "prompt template {:?} is missing the {PLACEHOLDER} marker",
+                template.name
+            )));
+        }
+    }
+    if !file.templates.iter().any(|t| t.name == file.active) {
+        return Err(AppError::Storage(format!(
+            "active prompt template {:?} does not exist",
+            file.active
+        )));
+    }
+    Ok(())
+}
+
+impl PromptStore {
+    /// Load templates from disk, writing the defaults on first launch.
+    pub fn open(app_data_dir: &Path) -> Result<Self, AppError> {
+        std::fs::create_dir_all(app_data_dir)
+            .map_err(|e| AppError::Storage(format!("failed to create app data dir: {e}")))?;
+        let path = app_data_dir.join("prompts.json");
+        let file = if path.is_file() {
+            let raw = std::fs::read_to_string(&path)
+                .map_err(|e| AppError::Storage(format!("failed to read prompts: {e}")))?;
+            serde_json::from_str(&raw)
+                .map_err(|e| AppError::Storage(format!("corrupt prompts file: {e}")))?
+        } else {
+            let defaults = default_templates();
+            write_prompts(&path, &defaults)?;
+            defaults
+        };
+        validate_file(&file)?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// All templates in file order.
+    pub fn list(&self) -> Vec<PromptTemplate> {
+        self.file.lock().unwrap().templates.clone()
+    }
+
+    pub fn active(&self) -> String {
+        self.file.lock().unwrap().active.clone()
+    }
+
+    /// Make `name` the active template and persist the choice.
+    pub fn set_active(&self, name: &str) -> Result<(), AppError> {
+        let mut file = self.file.lock().unwrap();
+        if !file.templates.iter().any(|t| t.name == name) {
+            return Err(AppError::InvalidInput(format!(
+                "no prompt template named {name:?}"
+            )));
+        }
+        file.active = name.to_string();
+        write_prompts(&self.path, &file)
+    }
+
+    /// Wrap `text` in the active template.
+    ///
+    /// `text` is inserted verbatim at each `{input}` marker; it is
+    /// never itself treated as template syntax.
+    pub fn render(&self, text: &str) -> RenderedPrompt {
+        let file = self.file.lock().unwrap();
+        let template = file
+            .templates
+            .iter()
+            .find(|t| t.name == file.active)
+            .expect("active template validated at load time");
+        RenderedPrompt {
+            system: (!template.system.is_empty()).then(|| template.system.clone()),
+            prompt: template.user.replace(PLACEHOLDER, text),
+        }
+    }
+}
+
+fn write_prompts(path: &Path, file: &PromptsFile) -> Result<(), AppError> {
+    let encoded = serde_json::to_string_pretty(file)
+        .map_err(|e| AppError::Internal(format!("failed to encode prompts: {e}")))?;
+    std::fs::write(path, encoded)
+        .map_err(|e| AppError::Storage(format!("failed to write prompts: {e}")))
+}
+
+/// Entry in the template list shown to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptTemplateInfo {
+    pub name: String,
+    pub system: String,
+    pub user: String,
+    pub active: bool,
+}
+
+#[tauri::command]
+pub fn list_prompt_templates(
+    store: tauri::State<'_, PromptStore>,
+) -> Vec<PromptTemplateInfo> {
+    let active = store.active();
+    store
+        .list()
+        .into_iter()
+        .map(|t| PromptTemplateInfo {
+            active: t.name == active,
+            name: t.name,
+            system: t.system,
+            user: t.user,
+        })
+        .collect()
+}
+
+/// Switch which template `generate_stream` wraps prompts in.
+#[tauri::command]
+pub fn set_prompt_template(
+    name: String,
+    store: tauri::State<'_, PromptStore>,
+) -> Result<(), AppError> {
+    store.set_active(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> PromptStore {
+        let dir = std::env::temp_dir().join(format!("prompts-test-{}", uuid::Uuid::new_v4()));
+        PromptStore::open(&dir).unwrap()
+    }
+
+    #[test]
+    fn plain_template_passes_input_through() {
+        let store = store();
+        let rendered = store.render("list my files");
+        assert_eq!(rendered.prompt, "list my files");
+        assert!(rendered.system.is_none());
+    }
+
+    #[test]
+    fn input_is_not_interpreted_as_template_syntax() {
+        let store = store();
+        store.set_active("assistant").unwrap();
+        let rendered = store.render("echo {input} and {system}");
+        // The marker in the input survives verbatim instead of
+        // recursing or pulling in template fields.
+        assert_eq!(rendered.prompt, "echo {input} and {system}");
+        assert!(rendered.system.unwrap().contains("TinyLlama-X"));
+    }
+
+    #[test]
+    fn unknown_template_is_rejected() {
+        let store = store();
+        assert!(matches!(
+            store.set_active("nope"),
+            Err(AppError::InvalidInput(_))
+        ));
+    }
+}
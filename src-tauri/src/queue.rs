@@ -0,0 +1,189 @@
+//! Serialized execution queue.
+//!
+//! Plans never run concurrently: `execute_plan` enqueues and returns
+//! the plan id immediately, and a single background worker drains the
+//! queue in FIFO order, emitting `"plan-started"` / `"plan-finished"`
+//! events around each run. Pending plans can be listed and removed;
+//! the one currently running cannot.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
+
+use crate::error::AppError;
+use crate::exec::ExecutionOutcome;
+use crate::plan::Plan;
+
+/// One queue entry as shown to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueuedPlan {
+    pub plan_id: String,
+    pub description: String,
+    pub command: String,
+    pub args: Vec<String>,
+    /// True for the entry the worker is currently executing.
+    pub running: bool,
+}
+
+/// Payload for the `"plan-finished"` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanFinished {
+    pub plan_id: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<ExecutionOutcome>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Managed queue state shared between commands and the worker.
+#[derive(Default)]
+pub struct ExecQueue {
+    pending: Mutex<VecDeque<Plan>>,
+    running: Mutex<Option<Plan>>,
+    notify: Notify,
+}
+
+impl ExecQueue {
+    /// Add a plan to the back of the queue and wake the worker.
+    pub fn enqueue(&self, plan: Plan) -> String {
+        let plan_id = plan.id.clone();
+        self.pending.lock().unwrap().push_back(plan);
+        self.notify.notify_one();
+        plan_id
+    }
+
+    /// Pop the next plan and mark it running, if any.
+    fn pop_and_mark_running(&self) -> Option<Plan> {
+        let plan = self.pending.lock().unwrap().pop_front()?;
+        *self.running.lock().unwrap() = Some(plan.clone());
+        Some(plan)
+    }
+
+    fn clear_running(&self) {
+        *self.running.lock().unwrap() = None;
+    }
+
+    /// The plan the worker is executing right now, if any.
+    pub fn current(&self) -> Option<Plan> {
+        self.running.lock().unwrap().clone()
+    }
+
+    fn entry(plan: &Plan, running: bool) -> QueuedPlan {
+        QueuedPlan {
+            plan_id: plan.id.clone(),
+            description: plan.description.clone(),
+            command: plan.command.clone(),
+            args: plan.args.clone(),
+            running,
+        }
+    }
+
+    /// Running entry first, then pending in execution order.
+    pub fn list(&self) -> Vec<QueuedPlan> {
+        let mut out = Vec::new();
+        if let Some(plan) = self.running.lock().unwrap().as_ref() {
+            out.push(Self::entry(plan, true));
+        }
+        out.extend(
+            self.pending
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|p| Self::entry(p, false)),
+        );
+        out
+    }
+
+    /// Remove a pending plan. Plans that already started can only be
+    /// stopped through their own timeout; removing them here would
+    /// leave the worker running an unlisted command.
+    pub fn dequeue(&self, plan_id: &str) -> Result<(), AppError> {
+        if self
+            .running
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|p| p.id == plan_id)
+        {
+            return Err(AppError::InvalidInput(format!(
+                "plan {plan_id} is already running and cannot be dequeued"
+            )));
+        }
+        let mut pending = self.pending.lock().unwrap();
+        let before = pending.len();
+        pending.retain(|p| p.id != plan_id);
+        if pending.len() == before {
+            return Err(AppError::InvalidInput(format!(
+                "plan {plan_id} is not queued"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Drain the queue forever, one plan at a time. Spawned once in setup.
+pub fn spawn_worker(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let plan = {
+                let queue = app.state::<ExecQueue>();
+                match queue.pop_and_mark_running() {
+                    Some(plan) => plan,
+                    None => {
+                        queue.notify.notified().await;
+                        continue;
+                    }
+                }
+            };
+            let plan_id = plan.id.clone();
+            let description = plan.description.clone();
+            let _ = app.emit("plan-started", &plan_id);
+            let result = crate::exec::run_plan(&app, plan).await;
+            app.state::<ExecQueue>().clear_running();
+            let payload = match result {
+                Ok(outcome) => {
+                    app.state::<crate::clipboard::LastResult>()
+                        .remember(outcome.stdout.clone());
+                    PlanFinished {
+                        plan_id,
+                        success: outcome.exit_code == Some(0),
+                        outcome: Some(outcome),
+                        error: None,
+                    }
+                }
+                Err(e) => PlanFinished {
+                    plan_id,
+                    success: false,
+                    outcome: None,
+                    error: Some(e.to_string()),
+                },
+            };
+            crate::notify::notify_plan_finished(
+                &app,
+                &payload.plan_id,
+                &description,
+                payload.success,
+            );
+            let _ = app.emit("plan-finished", &payload);
+        }
+    });
+}
+
+/// Pending and running queue entries, for the UI's pending list.
+#[tauri::command]
+pub fn list_queue(queue: tauri::State<'_, ExecQueue>) -> Result<Vec<QueuedPlan>, AppError> {
+    Ok(queue.list())
+}
+
+/// Remove a not-yet-started plan from the queue.
+#[tauri::command]
+pub fn dequeue_plan(
+    plan_id: String,
+    queue: tauri::State<'_, ExecQueue>,
+) -> Result<(), AppError> {
+    queue.dequeue(&plan_id)
+}
@@ -0,0 +1,65 @@
+//! Resource-usage sampling for the app and the managed sidecar.
+//!
+//! The frontend polls `resource_usage` every few seconds to chart RSS
+//! and CPU. `sysinfo` computes CPU percent from the delta between two
+//! refreshes, so the `System` handle lives in managed state — the
+//! previous sample is the baseline for the next call.
+
+use std::sync::Mutex;
+
+use serde::Serialize;
+use sysinfo::{Pid, ProcessRefreshKind, System};
+
+use crate::error::AppError;
+use crate::sidecar::SidecarState;
+
+/// One sample of process resource usage. Sidecar fields are `None`
+/// when no sidecar is currently managed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceSnapshot {
+    pub app_rss_bytes: u64,
+    pub app_cpu_percent: f32,
+    pub sidecar_rss_bytes: Option<u64>,
+    pub sidecar_cpu_percent: Option<f32>,
+}
+
+/// Managed sampler holding the baseline for CPU deltas.
+#[derive(Default)]
+pub struct ResourceMonitor {
+    system: Mutex<System>,
+}
+
+impl ResourceMonitor {
+    fn sample_pid(&self, system: &mut System, pid: u32) -> Option<(u64, f32)> {
+        let pid = Pid::from_u32(pid);
+        system.refresh_process_specifics(pid, ProcessRefreshKind::new().with_cpu().with_memory());
+        system
+            .process(pid)
+            .map(|p| (p.memory(), p.cpu_usage()))
+    }
+
+    pub fn snapshot(&self, sidecar_pid: Option<u32>) -> Result<ResourceSnapshot, AppError> {
+        let mut system = self.system.lock().unwrap();
+        let (app_rss_bytes, app_cpu_percent) = self
+            .sample_pid(&mut system, std::process::id())
+            .ok_or_else(|| AppError::Internal("cannot sample own process".into()))?;
+        let sidecar = sidecar_pid.and_then(|pid| self.sample_pid(&mut system, pid));
+        Ok(ResourceSnapshot {
+            app_rss_bytes,
+            app_cpu_percent,
+            sidecar_rss_bytes: sidecar.map(|(rss, _)| rss),
+            sidecar_cpu_percent: sidecar.map(|(_, cpu)| cpu),
+        })
+    }
+}
+
+/// Sample current RSS and CPU for the app and, when managed, the
+/// sidecar. The first call after startup reports 0% CPU since there is
+/// no previous sample to diff against.
+#[tauri::command]
+pub fn resource_usage(
+    monitor: tauri::State<'_, ResourceMonitor>,
+    sidecar: tauri::State<'_, SidecarState>,
+) -> Result<ResourceSnapshot, AppError> {
+    monitor.snapshot(sidecar.pid())
+}
@@ -0,0 +1,129 @@
+//! File snapshots and rollback for file-modifying plans.
+//!
+//! Before a plan the simulation marked as file-touching runs, the
+//! affected files are copied into a per-plan backup directory under app
+//! data. `undo_plan` restores them — including deleting files the plan
+//! created. Read-only plans never snapshot, so no disk is wasted.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// One backed-up file in a snapshot manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupFile {
+    /// Where the file lives in the real tree.
+    original: PathBuf,
+    /// File name inside the backup directory, when the file existed.
+    stored: Option<String>,
+}
+
+/// Managed backup storage rooted at `app_data_dir/backups`.
+pub struct BackupStore {
+    root: PathBuf,
+}
+
+impl BackupStore {
+    pub fn open(app_data_dir: &Path) -> Result<Self, AppError> {
+        let root = app_data_dir.join("backups");
+        std::fs::create_dir_all(&root)
+            .map_err(|e| AppError::Storage(format!("failed to create backup dir: {e}")))?;
+        Ok(Self { root })
+    }
+
+    fn plan_dir(&self, plan_id: &str) -> PathBuf {
+        // Plan ids are UUIDs we mint ourselves, but sanitize anyway so a
+        // crafted id can't escape the backup root.
+        let safe: String = plan_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+            .collect();
+        self.root.join(safe)
+    }
+
+    /// Snapshot `files` before plan `plan_id` runs. Files that don't
+    /// exist yet are recorded so undo can remove them again.
+    pub fn snapshot(&self, plan_id: &str, files: &[PathBuf]) -> Result<(), AppError> {
+        let dir = self.plan_dir(plan_id);
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| AppError::Storage(format!("failed to create backup dir: {e}")))?;
+
+        let mut manifest = Vec::with_capacity(files.len());
+        for (idx, original) in files.iter().enumerate() {
+            let stored = if original.is_file() {
+                let name = format!("{idx}.orig");
+                std::fs::copy(original, dir.join(&name)).map_err(|e| {
+                    AppError::Storage(format!(
+                        "failed to back up {}: {e}",
+                        original.display()
+                    ))
+                })?;
+                Some(name)
+            } else {
+                None
+            };
+            manifest.push(BackupFile {
+                original: original.clone(),
+                stored,
+            });
+        }
+
+        let encoded = serde_json::to_string(&manifest)
+            .map_err(|e| AppError::Internal(format!("failed to encode manifest: {e}")))?;
+        std::fs::write(dir.join("manifest.json"), encoded)
+            .map_err(|e| AppError::Storage(format!("failed to write manifest: {e}")))
+    }
+
+    /// Restore the snapshot for `plan_id`, then drop the backup.
+    pub fn restore(&self, plan_id: &str) -> Result<(), AppError> {
+        let dir = self.plan_dir(plan_id);
+        let manifest_path = dir.join("manifest.json");
+        if !manifest_path.is_file() {
+            return Err(AppError::BackupMissing(plan_id.to_string()));
+        }
+        let manifest: Vec<BackupFile> = serde_json::from_str(
+            &std::fs::read_to_string(&manifest_path)
+                .map_err(|e| AppError::Storage(format!("failed to read manifest: {e}")))?,
+        )
+        .map_err(|e| AppError::Storage(format!("corrupt backup manifest: {e}")))?;
+
+        for file in &manifest {
+            match &file.stored {
+                Some(name) => {
+                    std::fs::copy(dir.join(name), &file.original).map_err(|e| {
+                        AppError::Storage(format!(
+                            "failed to restore {}: {e}",
+                            file.original.display()
+                        ))
+                    })?;
+                }
+                // The file didn't exist before the plan: undo means
+                // removing whatever the plan created.
+                None => {
+                    if file.original.exists() {
+                        std::fs::remove_file(&file.original).map_err(|e| {
+                            AppError::Storage(format!(
+                                "failed to remove {}: {e}",
+                                file.original.display()
+                            ))
+                        })?;
+                    }
+                }
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+        Ok(())
+    }
+}
+
+/// Roll back the file changes of an executed plan.
+#[tauri::command]
+pub fn undo_plan(
+    plan_id: String,
+    backups: tauri::State<'_, BackupStore>,
+) -> Result<(), AppError> {
+    backups.restore(&plan_id)
+}
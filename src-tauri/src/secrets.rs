@@ -0,0 +1,44 @@
+//! API key storage in the OS keychain.
+//!
+//! The raw key never crosses to the frontend: there is a setter and an
+//! existence check, nothing else. The bridge picks the key up from
+//! managed state and attaches it as a bearer token.
+
+use keyring::Entry;
+
+use crate::bridge::Bridge;
+use crate::error::AppError;
+
+const SERVICE: &str = "tinyllama-x";
+const ACCOUNT: &str = "backend-api-key";
+
+fn entry() -> Result<Entry, AppError> {
+    Entry::new(SERVICE, ACCOUNT)
+        .map_err(|e| AppError::Internal(format!("keychain unavailable: {e}")))
+}
+
+/// Read the stored key at startup, if any. A missing entry is normal.
+pub fn load() -> Result<Option<String>, AppError> {
+    match entry()?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AppError::Internal(format!("keychain read failed: {e}"))),
+    }
+}
+
+/// Store the backend API key in the OS keychain and start using it for
+/// subsequent requests.
+#[tauri::command]
+pub fn set_api_key(key: String, bridge: tauri::State<'_, Bridge>) -> Result<(), AppError> {
+    entry()?
+        .set_password(&key)
+        .map_err(|e| AppError::Internal(format!("keychain write failed: {e}")))?;
+    bridge.set_auth_token(Some(key));
+    Ok(())
+}
+
+/// Whether a key is stored, without revealing it.
+#[tauri::command]
+pub fn has_api_key() -> Result<bool, AppError> {
+    Ok(load()?.is_some())
+}
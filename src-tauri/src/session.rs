@@ -0,0 +1,151 @@
+//! Session export/import for reproducible bug reports.
+//!
+//! `export_session` bundles settings, recent history, audit entries and
+//! a metrics snapshot into one zip. Keychain secrets are never part of
+//! the bundle — the API key lives only in the OS keychain, which none
+//! of the exported stores touch. `import_session` parses and validates
+//! the whole archive before applying anything, so an incompatible
+//! bundle is refused outright instead of half-restored.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit::{AuditEntry, AuditLog};
+use crate::error::AppError;
+use crate::history::{Exchange, HistoryDb};
+use crate::metrics::Metrics;
+use crate::settings::{Settings, SettingsStore};
+
+/// Bumped whenever the bundle layout changes incompatibly.
+const FORMAT_VERSION: u32 = 1;
+
+/// How much history / audit goes into a bundle.
+const EXPORT_HISTORY_LIMIT: u32 = 200;
+const EXPORT_AUDIT_LIMIT: u32 = 500;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    format_version: u32,
+    exported_at_ms: i64,
+}
+
+fn storage(e: impl std::fmt::Display) -> AppError {
+    AppError::Storage(format!("session bundle: {e}"))
+}
+
+fn check_path(path: &Path) -> Result<(), AppError> {
+    let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+    if !parent.map(Path::is_dir).unwrap_or(false) {
+        return Err(AppError::InvalidInput(format!(
+            "directory for {} does not exist",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Export the current session into a zip at `path`. Secrets are
+/// excluded by construction.
+#[tauri::command]
+pub fn export_session(
+    path: PathBuf,
+    settings: tauri::State<'_, SettingsStore>,
+    history: tauri::State<'_, HistoryDb>,
+    audit_log: tauri::State<'_, AuditLog>,
+    metrics: tauri::State<'_, Metrics>,
+) -> Result<(), AppError> {
+    check_path(&path)?;
+    let file = std::fs::File::create(&path).map_err(storage)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default();
+
+    let mut write_entry = |name: &str, body: String| -> Result<(), AppError> {
+        zip.start_file(name, options).map_err(storage)?;
+        zip.write_all(body.as_bytes()).map_err(storage)
+    };
+
+    write_entry(
+        "manifest.json",
+        serde_json::to_string_pretty(&Manifest {
+            format_version: FORMAT_VERSION,
+            exported_at_ms: crate::audit::now_ms(),
+        })
+        .map_err(storage)?,
+    )?;
+    write_entry(
+        "settings.json",
+        serde_json::to_string_pretty(&settings.get()).map_err(storage)?,
+    )?;
+    write_entry(
+        "history.json",
+        serde_json::to_string_pretty(&history.list(EXPORT_HISTORY_LIMIT, 0)?).map_err(storage)?,
+    )?;
+    write_entry(
+        "audit.json",
+        serde_json::to_string_pretty(&audit_log.tail(EXPORT_AUDIT_LIMIT)?).map_err(storage)?,
+    )?;
+    write_entry(
+        "metrics.json",
+        serde_json::to_string_pretty(&metrics.snapshot()).map_err(storage)?,
+    )?;
+    zip.finish().map_err(storage)?;
+    Ok(())
+}
+
+/// Restore a previously exported bundle. The whole archive is parsed
+/// and version-checked before any state is touched.
+#[tauri::command]
+pub fn import_session(
+    path: PathBuf,
+    settings: tauri::State<'_, SettingsStore>,
+    history: tauri::State<'_, HistoryDb>,
+    audit_log: tauri::State<'_, AuditLog>,
+) -> Result<(), AppError> {
+    let file = std::fs::File::open(&path)
+        .map_err(|e| AppError::InvalidInput(format!("cannot open {}: {e}", path.display())))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| AppError::InvalidInput(format!("not a session bundle: {e}")))?;
+
+    let mut read_entry = |name: &str| -> Result<String, AppError> {
+        let mut entry = archive.by_name(name).map_err(|_| {
+            AppError::InvalidInput(format!("bundle is missing {name}; refusing to import"))
+        })?;
+        let mut body = String::new();
+        entry.read_to_string(&mut body).map_err(storage)?;
+        Ok(body)
+    };
+
+    // Parse everything up front so a bad bundle changes nothing.
+    let manifest: Manifest = serde_json::from_str(&read_entry("manifest.json")?)
+        .map_err(|e| AppError::InvalidInput(format!("corrupt manifest: {e}")))?;
+    if manifest.format_version != FORMAT_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "bundle format v{} is not supported (expected v{FORMAT_VERSION})",
+            manifest.format_version
+        )));
+    }
+    let imported_settings: Settings = serde_json::from_str(&read_entry("settings.json")?)
+        .map_err(|e| AppError::InvalidInput(format!("corrupt settings in bundle: {e}")))?;
+    // Same semantic checks update_settings enforces; a stale or crafted
+    // bundle must not smuggle in values the UI could never set.
+    crate::settings::validate_settings(&imported_settings)
+        .map_err(|e| AppError::InvalidInput(format!("invalid settings in bundle: {e}")))?;
+    let exchanges: Vec<Exchange> = serde_json::from_str(&read_entry("history.json")?)
+        .map_err(|e| AppError::InvalidInput(format!("corrupt history in bundle: {e}")))?;
+    let entries: Vec<AuditEntry> = serde_json::from_str(&read_entry("audit.json")?)
+        .map_err(|e| AppError::InvalidInput(format!("corrupt audit log in bundle: {e}")))?;
+
+    settings.replace(imported_settings)?;
+    // list() returns newest first; re-insert oldest first to keep order.
+    for exchange in exchanges.iter().rev() {
+        history.save(exchange)?;
+    }
+    for entry in entries.iter().rev() {
+        audit_log.record(entry)?;
+    }
+    // The metrics snapshot is for the bug report reader; counters start
+    // fresh on the importing install.
+    Ok(())
+}
@@ -0,0 +1,348 @@
+//! Persistent user settings backed by `app_data_dir/settings.json`.
+//!
+//! Settings are loaded once at startup (defaults are written out on
+//! first launch) and seed the managed [`BridgeConfig`]. Updates are
+//! partial: the frontend sends a patch with only the fields it changed,
+//! the patch is validated and merged, and the whole file is rewritten.
+//! Bridge-level fields take effect on the next launch since the bridge
+//! client is built once at startup.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bridge::{BridgeConfig, Transport};
+use crate::error::AppError;
+
+/// Everything the user can configure, with serde defaults so an older
+/// settings file from a previous version still loads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub bridge: BridgeConfig,
+    #[serde(default = "default_theme")]
+    pub theme: String,
+    #[serde(default = "default_true")]
+    pub notify_on_completion: bool,
+    /// Working directory for executed plans; path arguments may not
+    /// resolve outside it.
+    #[serde(default = "default_sandbox_root")]
+    pub sandbox_root: PathBuf,
+    /// Hard wall-clock limit for one executed plan before its process
+    /// group is killed.
+    #[serde(default = "default_exec_timeout_ms")]
+    pub exec_timeout_ms: u64,
+    /// Longest user input (in characters) accepted by backend-bound
+    /// commands.
+    #[serde(default = "default_max_input_chars")]
+    pub max_input_chars: usize,
+    /// How many backend requests may run concurrently.
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+    /// Loopback port for the external status endpoint; 0 (the default)
+    /// leaves it disabled. Takes effect on the next launch.
+    #[serde(default)]
+    pub status_port: u16,
+}
+
+fn default_theme() -> String {
+    "system".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_exec_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_max_input_chars() -> usize {
+    crate::input::DEFAULT_MAX_INPUT_CHARS
+}
+
+fn default_max_concurrent_requests() -> usize {
+    4
+}
+
+fn default_sandbox_root() -> PathBuf {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/"))
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            bridge: BridgeConfig::default(),
+            theme: default_theme(),
+            notify_on_completion: default_true(),
+            sandbox_root: default_sandbox_root(),
+            exec_timeout_ms: default_exec_timeout_ms(),
+            max_input_chars: default_max_input_chars(),
+            max_concurrent_requests: default_max_concurrent_requests(),
+            status_port: 0,
+        }
+    }
+}
+
+/// Partial update: only fields present in the patch are applied.
+#[derive(Debug, Default, Deserialize)]
+pub struct SettingsPatch {
+    pub endpoints: Option<Vec<String>>,
+    pub request_timeout_ms: Option<u64>,
+    pub max_retries: Option<u32>,
+    pub backoff_ms: Option<u64>,
+    pub transport: Option<Transport>,
+    pub min_confidence: Option<f32>,
+    pub theme: Option<String>,
+    pub notify_on_completion: Option<bool>,
+    pub sandbox_root: Option<PathBuf>,
+    pub exec_timeout_ms: Option<u64>,
+    pub max_input_chars: Option<usize>,
+    pub max_concurrent_requests: Option<usize>,
+    pub status_port: Option<u16>,
+}
+
+/// Validate a complete settings value with the same rules patches get,
+/// e.g. one imported from a session bundle. A zero timeout or
+/// concurrency limit would brick every later command, so a bad value
+/// is rejected rather than persisted.
+pub fn validate_settings(settings: &Settings) -> Result<(), AppError> {
+    validate(&SettingsPatch {
+        endpoints: Some(settings.bridge.endpoints.clone()),
+        request_timeout_ms: Some(settings.bridge.request_timeout_ms),
+        max_retries: Some(settings.bridge.max_retries),
+        backoff_ms: Some(settings.bridge.backoff_ms),
+        transport: Some(settings.bridge.transport),
+        min_confidence: Some(settings.bridge.min_confidence),
+        theme: Some(settings.theme.clone()),
+        notify_on_completion: Some(settings.notify_on_completion),
+        sandbox_root: Some(settings.sandbox_root.clone()),
+        exec_timeout_ms: Some(settings.exec_timeout_ms),
+        max_input_chars: Some(settings.max_input_chars),
+        max_concurrent_requests: Some(settings.max_concurrent_requests),
+        status_port: Some(settings.status_port),
+    })
+}
+
+/// Reject a patch before anything is merged, so settings on disk are
+/// never half-updated.
+fn validate(patch: &SettingsPatch) -> Result<(), AppError> {
+    if let Some(endpoints) = &patch.endpoints {
+        if endpoints.is_empty() {
+            return Err(AppError::InvalidInput(
+                "endpoints must contain at least one URL".into(),
+            ));
+        }
+        for endpoint in endpoints {
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                return Err(AppError::InvalidInput(format!(
+                    "endpoint {endpoint:?} is not an http(s) URL"
+                )));
+            }
+        }
+    }
+    if patch.request_timeout_ms == Some(0) {
+        return Err(AppError::InvalidInput(
+            "request_timeout_ms must be greater than zero".into(),
+        ));
+    }
+    if patch.exec_timeout_ms == Some(0) {
+        return Err(AppError::InvalidInput(
+            "exec_timeout_ms must be greater than zero".into(),
+        ));
+    }
+    if patch.max_concurrent_requests == Some(0) {
+        return Err(AppError::InvalidInput(
+            "max_concurrent_requests must be greater than zero".into(),
+        ));
+    }
+    if patch.max_input_chars == Some(0) {
+        return Err(AppError::InvalidInput(
+            "max_input_chars must be greater than zero".into(),
+        ));
+    }
+    if patch.backoff_ms == Some(0) {
+        return Err(AppError::InvalidInput(
+            "backoff_ms must be greater than zero".into(),
+        ));
+    }
+    if let Some(c) = patch.min_confidence {
+        if !(0.0..=1.0).contains(&c) {
+            return Err(AppError::InvalidInput(format!(
+                "min_confidence must be between 0 and 1, got {c}"
+            )));
+        }
+    }
+    if let Some(theme) = &patch.theme {
+        if !matches!(theme.as_str(), "system" | "light" | "dark") {
+            return Err(AppError::InvalidInput(format!(
+                "unknown theme {theme:?}; expected system, light or dark"
+            )));
+        }
+    }
+    if let Some(root) = &patch.sandbox_root {
+        if !root.is_dir() {
+            return Err(AppError::InvalidInput(format!(
+                "sandbox_root {} is not an existing directory",
+                root.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Managed settings storage.
+pub struct SettingsStore {
+    path: PathBuf,
+    current: Mutex<Settings>,
+}
+
+impl SettingsStore {
+    /// Load settings from disk, writing defaults on first launch.
+    pub fn open(app_data_dir: &Path) -> Result<Self, AppError> {
+        std::fs::create_dir_all(app_data_dir)
+            .map_err(|e| AppError::Storage(format!("failed to create app data dir: {e}")))?;
+        let path = app_data_dir.join("settings.json");
+        let settings = if path.is_file() {
+            let raw = std::fs::read_to_string(&path)
+                .map_err(|e| AppError::Storage(format!("failed to read settings: {e}")))?;
+            serde_json::from_str(&raw)
+                .map_err(|e| AppError::Storage(format!("corrupt settings file: {e}")))?
+        } else {
+            let defaults = Settings::default();
+            write_settings(&path, &defaults)?;
+            defaults
+        };
+        Ok(Self {
+            path,
+            current: Mutex::new(settings),
+        })
+    }
+
+    pub fn get(&self) -> Settings {
+        self.current.lock().unwrap().clone()
+    }
+
+    /// Replace the settings wholesale, e.g. from an imported session
+    /// bundle, and persist them. The value gets the same validation a
+    /// patch would.
+    pub fn replace(&self, settings: Settings) -> Result<(), AppError> {
+        validate_settings(&settings)?;
+        write_settings(&self.path, &settings)?;
+        *self.current.lock().unwrap() = settings;
+        Ok(())
+    }
+
+    /// Validate, merge and persist a patch, returning the new settings.
+    pub fn apply(&self, patch: SettingsPatch) -> Result<Settings, AppError> {
+        validate(&patch)?;
+        let mut current = self.current.lock().unwrap();
+        let mut next = current.clone();
+        if let Some(endpoints) = patch.endpoints {
+            next.bridge.endpoints = endpoints
+                .into_iter()
+                .map(|e| e.trim_end_matches('/').to_string())
+                .collect();
+        }
+        if let Some(v) = patch.request_timeout_ms {
+            next.bridge.request_timeout_ms = v;
+        }
+        if let Some(v) = patch.max_retries {
+            next.bridge.max_retries = v;
+        }
+        if let Some(v) = patch.backoff_ms {
+            next.bridge.backoff_ms = v;
+        }
+        if let Some(v) = patch.transport {
+            next.bridge.transport = v;
+        }
+        if let Some(v) = patch.min_confidence {
+            next.bridge.min_confidence = v;
+        }
+        if let Some(v) = patch.theme {
+            next.theme = v;
+        }
+        if let Some(v) = patch.notify_on_completion {
+            next.notify_on_completion = v;
+        }
+        if let Some(v) = patch.sandbox_root {
+            next.sandbox_root = v;
+        }
+        if let Some(v) = patch.exec_timeout_ms {
+            next.exec_timeout_ms = v;
+        }
+        if let Some(v) = patch.max_input_chars {
+            next.max_input_chars = v;
+        }
+        if let Some(v) = patch.max_concurrent_requests {
+            next.max_concurrent_requests = v;
+        }
+        if let Some(v) = patch.status_port {
+            next.status_port = v;
+        }
+        write_settings(&self.path, &next)?;
+        *current = next.clone();
+        Ok(next)
+    }
+}
+
+fn write_settings(path: &Path, settings: &Settings) -> Result<(), AppError> {
+    let encoded = serde_json::to_string_pretty(settings)
+        .map_err(|e| AppError::Internal(format!("failed to encode settings: {e}")))?;
+    std::fs::write(path, encoded)
+        .map_err(|e| AppError::Storage(format!("failed to write settings: {e}")))
+}
+
+#[tauri::command]
+pub fn get_settings(store: tauri::State<'_, SettingsStore>) -> Result<Settings, AppError> {
+    Ok(store.get())
+}
+
+/// Merge a partial update into the stored settings.
+#[tauri::command]
+pub fn update_settings(
+    patch: SettingsPatch,
+    store: tauri::State<'_, SettingsStore>,
+) -> Result<Settings, AppError> {
+    store.apply(patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_zero_timeout() {
+        let patch = SettingsPatch {
+            request_timeout_ms: Some(0),
+            ..Default::default()
+        };
+        assert!(matches!(validate(&patch), Err(AppError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn rejects_out_of_range_confidence() {
+        let patch = SettingsPatch {
+            min_confidence: Some(1.5),
+            ..Default::default()
+        };
+        assert!(matches!(validate(&patch), Err(AppError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn rejects_non_http_endpoint() {
+        let patch = SettingsPatch {
+            endpoints: Some(vec!["ftp://example".into()]),
+            ..Default::default()
+        };
+        assert!(matches!(validate(&patch), Err(AppError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn accepts_empty_patch() {
+        assert!(validate(&SettingsPatch::default()).is_ok());
+    }
+}
@@ -0,0 +1,90 @@
+//! Orderly teardown when the app quits.
+//!
+//! Without this, quitting mid-generation leaves the sidecar orphaned
+//! and a half-written log behind. The hook cancels in-flight streams,
+//! gives the currently executing plan a short grace period to finish,
+//! writes a final metrics snapshot to the log, and kills the managed
+//! sidecar. A plan that outlives the grace period keeps its process
+//! group alive only until the plan's own timeout; shutdown proceeds and
+//! the abandonment is recorded as an `"interrupted"` audit entry.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager};
+
+use crate::audit::{now_ms, AuditEntry, AuditLog};
+use crate::cancel::CancelRegistry;
+use crate::plan::Plan;
+use crate::queue::ExecQueue;
+
+/// How long the current queue item gets to finish before shutdown
+/// proceeds without it.
+const GRACE_MS: u64 = 2_000;
+
+/// Guards against running the teardown twice; `ExitRequested` and the
+/// final `Exit` event both route here.
+static DONE: AtomicBool = AtomicBool::new(false);
+
+/// Run the teardown once. Safe to call from both exit events.
+pub fn run(app: &AppHandle) {
+    if DONE.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    // Stop token streams and pending classifications first so nothing
+    // new reaches the backend while we wait on the queue.
+    if let Some(cancels) = app.try_state::<CancelRegistry>() {
+        cancels.cancel_all();
+    }
+
+    if let Some(plan) = tauri::async_runtime::block_on(wait_for_current_plan(app)) {
+        tracing::warn!(plan_id = %plan.id, "plan still running at shutdown; proceeding");
+        if let Some(log) = app.try_state::<AuditLog>() {
+            let _ = log.record(&AuditEntry {
+                timestamp: now_ms(),
+                plan_id: plan.id.clone(),
+                command: "interrupted".into(),
+                args: std::iter::once(plan.command).chain(plan.args).collect(),
+                allowed: true,
+                denied_reason: None,
+                exit_code: None,
+                stdout_bytes: 0,
+                stderr_bytes: 0,
+                duration_ms: GRACE_MS,
+            });
+        }
+    }
+
+    // The audit log flushes on every record; metrics are in-memory
+    // only, so a final snapshot goes to the log before the appender's
+    // guard drops with the process.
+    if let Some(metrics) = app.try_state::<crate::metrics::Metrics>() {
+        if let Ok(snapshot) = serde_json::to_string(&metrics.snapshot()) {
+            tracing::info!(metrics = %snapshot, "final metrics snapshot");
+        }
+    }
+
+    // Managed only when status_port is configured.
+    if let Some(server) = app.try_state::<crate::status::StatusServer>() {
+        server.stop();
+    }
+
+    if let Some(sidecar) = app.try_state::<crate::sidecar::SidecarState>() {
+        sidecar.kill();
+    }
+}
+
+/// Wait up to the grace period for the queue's current item to finish.
+/// Returns the plan that was abandoned, if one was.
+async fn wait_for_current_plan(app: &AppHandle) -> Option<Plan> {
+    let queue = app.try_state::<ExecQueue>()?;
+    let deadline = Instant::now() + Duration::from_millis(GRACE_MS);
+    while Instant::now() < deadline {
+        if queue.current().is_none() {
+            return None;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    queue.current()
+}
@@ -0,0 +1,121 @@
+//! Managed FastAPI presenter sidecar.
+//!
+//! Instead of asking the user to start the Python server by hand, the
+//! app can spawn it as a bundled sidecar, wait until `/health` answers,
+//! and kill it again on shutdown. A crash surfaces as a
+//! `"backend-down"` event so the frontend can show a reconnect banner.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+use tauri_plugin_shell::ShellExt;
+
+use crate::bridge::Bridge;
+use crate::error::AppError;
+
+/// How long to wait for `/health` after spawning before giving up.
+const STARTUP_TIMEOUT_SECS: u64 = 30;
+
+/// Managed handle to the sidecar child process.
+#[derive(Default)]
+pub struct SidecarState {
+    child: Mutex<Option<CommandChild>>,
+    /// Set while we are stopping on purpose, so the event pump doesn't
+    /// report an intentional kill as a crash.
+    stopping: AtomicBool,
+}
+
+impl SidecarState {
+    pub fn is_running(&self) -> bool {
+        self.child.lock().unwrap().is_some()
+    }
+
+    /// OS pid of the managed sidecar, if one is running.
+    pub fn pid(&self) -> Option<u32> {
+        self.child.lock().unwrap().as_ref().map(|c| c.pid())
+    }
+
+    /// Kill the sidecar if it is running. Safe to call repeatedly.
+    pub fn kill(&self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        if let Some(child) = self.child.lock().unwrap().take() {
+            let _ = child.kill();
+        }
+    }
+}
+
+/// Spawn the bundled presenter and wait until it reports healthy.
+#[tauri::command]
+pub async fn start_backend(
+    app: AppHandle,
+    state: tauri::State<'_, SidecarState>,
+    bridge: tauri::State<'_, Bridge>,
+) -> Result<(), AppError> {
+    if state.is_running() {
+        return Ok(());
+    }
+    state.stopping.store(false, Ordering::SeqCst);
+
+    let (mut rx, child) = app
+        .shell()
+        .sidecar("tinyllama-backend")
+        .map_err(|e| AppError::Internal(format!("sidecar not bundled: {e}")))?
+        .spawn()
+        .map_err(|e| AppError::Internal(format!("failed to spawn backend sidecar: {e}")))?;
+    *state.child.lock().unwrap() = Some(child);
+
+    // Pump sidecar output into our log and watch for unexpected exits.
+    let pump_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(line) | CommandEvent::Stderr(line) => {
+                    // Through tracing so it reaches the rolling log
+                    // file; stderr is discarded in windowed builds.
+                    tracing::info!(target: "backend", "{}", String::from_utf8_lossy(&line));
+                }
+                CommandEvent::Terminated(payload) => {
+                    let state = pump_handle.state::<SidecarState>();
+                    state.child.lock().unwrap().take();
+                    if !state.stopping.load(Ordering::SeqCst) {
+                        let _ = pump_handle.emit("backend-down", payload.code);
+                    }
+                    break;
+                }
+                _ => {}
+            }
+        }
+    });
+
+    // Don't report ready until /health actually answers.
+    let deadline =
+        std::time::Instant::now() + std::time::Duration::from_secs(STARTUP_TIMEOUT_SECS);
+    loop {
+        if bridge.health().await.reachable {
+            // Freshly (re)started backend: re-check version skew.
+            crate::compat::check_and_warn(&app).await;
+            return Ok(());
+        }
+        if !state.is_running() {
+            return Err(AppError::BackendUnreachable(
+                "backend sidecar exited before becoming healthy".into(),
+            ));
+        }
+        if std::time::Instant::now() >= deadline {
+            state.kill();
+            return Err(AppError::Timeout(format!(
+                "backend did not become healthy within {STARTUP_TIMEOUT_SECS}s"
+            )));
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    }
+}
+
+/// Stop the managed backend, if any.
+#[tauri::command]
+pub fn stop_backend(state: tauri::State<'_, SidecarState>) -> Result<(), AppError> {
+    state.kill();
+    Ok(())
+}
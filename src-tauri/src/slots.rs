@@ -0,0 +1,307 @@
+//! Typed slot validation for classification results.
+//!
+//! The backend returns `slots` as a flat string map, which pushed all
+//! parsing onto the frontend. This module coerces known slots against a
+//! per-intent schema loaded from `app_data_dir/slot_schema.json`
+//! (defaults written on first launch; editing the file covers new
+//! intents without a recompile). Anything the schema can't vouch for —
+//! unknown slots, enum mismatches, unparseable numbers, missing
+//! required slots — lands in the warnings list instead of being dropped
+//! silently, so the UI can ask the user to clarify.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bridge::IntentResult;
+use crate::error::AppError;
+
+/// What one slot must look like.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SlotType {
+    String,
+    Integer {
+        /// Inclusive lower bound, e.g. 1 for counts.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<i64>,
+    },
+    Boolean,
+    Enum { values: Vec<String> },
+}
+
+/// Schema entry for one slot of one intent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotSpec {
+    #[serde(flatten)]
+    pub slot_type: SlotType,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// On-disk shape of `slot_schema.json`: intent name to slot specs.
+#[derive(Debug, Serialize, Deserialize)]
+struct SchemaFile {
+    intents: HashMap<String, HashMap<String, SlotSpec>>,
+}
+
+fn default_schema() -> SchemaFile {
+    let spec = |slot_type, required| SlotSpec {
+        slot_type,
+        required,
+    };
+    let mut intents = HashMap::new();
+    intents.insert(
+        "deploy".into(),
+        HashMap::from([
+            (
+                "environment".into(),
+                spec(
+                    SlotType::Enum {
+                        values: vec!["dev".into(), "staging".into(), "prod".into()],
+                    },
+                    true,
+                ),
+            ),
+            ("count".into(), spec(SlotType::Integer { min: Some(1) }, false)),
+        ]),
+    );
+    intents.insert(
+        "package_install".into(),
+        HashMap::from([
+            ("package".into(), spec(SlotType::String, true)),
+            (
+                "manager".into(),
+                spec(
+                    SlotType::Enum {
+                        values: vec!["apt".into(), "dnf".into(), "pacman".into(), "brew".into()],
+                    },
+                    false,
+                ),
+            ),
+        ]),
+    );
+    intents.insert(
+        "file_search".into(),
+        HashMap::from([("pattern".into(), spec(SlotType::String, true))]),
+    );
+    SchemaFile { intents }
+}
+
+/// A slot value after coercion. Serializes untagged, so the frontend
+/// sees plain JSON strings, integers and booleans.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum SlotValue {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+}
+
+/// Typed slots plus everything the schema couldn't confirm.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidatedSlots {
+    pub intent: String,
+    pub values: HashMap<String, SlotValue>,
+    pub warnings: Vec<String>,
+}
+
+/// Managed schema, loaded once at startup.
+pub struct SlotSchemaStore {
+    schema: SchemaFile,
+}
+
+impl SlotSchemaStore {
+    /// Load the schema from disk, writing the defaults on first launch.
+    pub fn open(app_data_dir: &Path) -> Result<Self, AppError> {
+        std::fs::create_dir_all(app_data_dir)
+            .map_err(|e| AppError::Storage(format!("failed to create app data dir: {e}")))?;
+        let path = app_data_dir.join("slot_schema.json");
+        let schema = if path.is_file() {
+            let raw = std::fs::read_to_string(&path)
+                .map_err(|e| AppError::Storage(format!("failed to read slot schema: {e}")))?;
+            serde_json::from_str(&raw)
+                .map_err(|e| AppError::Storage(format!("corrupt slot schema: {e}")))?
+        } else {
+            let defaults = default_schema();
+            let encoded = serde_json::to_string_pretty(&defaults)
+                .map_err(|e| AppError::Internal(format!("failed to encode slot schema: {e}")))?;
+            std::fs::write(&path, encoded)
+                .map_err(|e| AppError::Storage(format!("failed to write slot schema: {e}")))?;
+            defaults
+        };
+        Ok(Self { schema })
+    }
+
+    /// Validate a classification result's slots against the schema.
+    pub fn validate(&self, result: &IntentResult) -> ValidatedSlots {
+        let Some(specs) = self.schema.intents.get(&result.intent) else {
+            // Unschematized intent: pass everything through as strings
+            // but say so, since nothing was actually checked.
+            let mut out = ValidatedSlots {
+                intent: result.intent.clone(),
+                values: result
+                    .slots
+                    .iter()
+                    .map(|(k, v)| (k.clone(), SlotValue::String(v.clone())))
+                    .collect(),
+                warnings: Vec::new(),
+            };
+            if !result.slots.is_empty() {
+                out.warnings.push(format!(
+                    "no slot schema for intent {:?}; slots passed through unvalidated",
+                    result.intent
+                ));
+            }
+            return out;
+        };
+
+        let mut values = HashMap::new();
+        let mut warnings = Vec::new();
+        for (name, raw) in &result.slots {
+            match specs.get(name) {
+                Some(spec) => match coerce(&spec.slot_type, raw) {
+                    Ok(value) => {
+                        values.insert(name.clone(), value);
+                    }
+                    Err(reason) => warnings.push(format!("slot {name:?}: {reason}")),
+                },
+                None => {
+                    // Keep the raw value so nothing is lost, but flag it.
+                    values.insert(name.clone(), SlotValue::String(raw.clone()));
+                    warnings.push(format!(
+                        "slot {name:?} is not in the schema for intent {:?}",
+                        result.intent
+                    ));
+                }
+            }
+        }
+        for (name, spec) in specs {
+            if spec.required && !result.slots.contains_key(name) {
+                warnings.push(format!("required slot {name:?} is missing"));
+            }
+        }
+        ValidatedSlots {
+            intent: result.intent.clone(),
+            values,
+            warnings,
+        }
+    }
+}
+
+/// Coerce one raw value against its spec, or say why it can't be.
+fn coerce(slot_type: &SlotType, raw: &str) -> Result<SlotValue, String> {
+    match slot_type {
+        SlotType::String => Ok(SlotValue::String(raw.to_string())),
+        SlotType::Integer { min } => {
+            let value: i64 = raw
+                .trim()
+                .parse()
+                .map_err(|_| format!("{raw:?} is not an integer"))?;
+            if let Some(min) = min {
+                if value < *min {
+                    return Err(format!("{value} is below the minimum of {min}"));
+                }
+            }
+            Ok(SlotValue::Integer(value))
+        }
+        SlotType::Boolean => match raw.trim().to_ascii_lowercase().as_str() {
+            "true" | "yes" | "1" => Ok(SlotValue::Boolean(true)),
+            "false" | "no" | "0" => Ok(SlotValue::Boolean(false)),
+            _ => Err(format!("{raw:?} is not a boolean")),
+        },
+        SlotType::Enum { values } => {
+            if values.iter().any(|v| v == raw) {
+                Ok(SlotValue::String(raw.to_string()))
+            } else {
+                Err(format!("{raw:?} is not one of {values:?}"))
+            }
+        }
+    }
+}
+
+/// Validate and coerce a classification result's slots.
+#[tauri::command]
+pub fn validate_slots(
+    result: IntentResult,
+    schema: tauri::State<'_, SlotSchemaStore>,
+) -> ValidatedSlots {
+    schema.validate(&result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> SlotSchemaStore {
+        SlotSchemaStore {
+            schema: default_schema(),
+        }
+    }
+
+    fn result(intent: &str, slots: &[(&str, &str)]) -> IntentResult {
+        IntentResult {
+            intent: intent.into(),
+            confidence: 0.9,
+            slots: slots
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn coerces_valid_slots() {
+        let validated = store().validate(&result(
+            "deploy",
+            &[("environment", "staging"), ("count", "3")],
+        ));
+        assert!(validated.warnings.is_empty());
+        assert_eq!(
+            validated.values["environment"],
+            SlotValue::String("staging".into())
+        );
+        assert_eq!(validated.values["count"], SlotValue::Integer(3));
+    }
+
+    #[test]
+    fn bad_values_become_warnings_not_values() {
+        let validated = store().validate(&result(
+            "deploy",
+            &[("environment", "production"), ("count", "many")],
+        ));
+        assert!(!validated.values.contains_key("environment"));
+        assert!(!validated.values.contains_key("count"));
+        assert_eq!(validated.warnings.len(), 2);
+    }
+
+    #[test]
+    fn unknown_slot_is_kept_and_flagged() {
+        let validated = store().validate(&result(
+            "deploy",
+            &[("environment", "dev"), ("region", "eu-west-1")],
+        ));
+        assert_eq!(
+            validated.values["region"],
+            SlotValue::String("eu-west-1".into())
+        );
+        assert!(validated.warnings.iter().any(|w| w.contains("region")));
+    }
+
+    #[test]
+    fn missing_required_slot_warns() {
+        let validated = store().validate(&result("package_install", &[]));
+        assert!(validated
+            .warnings
+            .iter()
+            .any(|w| w.contains("package") && w.contains("missing")));
+    }
+
+    #[test]
+    fn zero_count_violates_minimum() {
+        let validated = store().validate(&result("deploy", &[("count", "0")]));
+        assert!(validated.warnings.iter().any(|w| w.contains("minimum")));
+    }
+}
@@ -0,0 +1,120 @@
+//! Optional local HTTP endpoint for external health checks.
+//!
+//! Kiosk and remote installs want to poll the app without driving the
+//! frontend, so when `status_port` is set the backend binds a tiny
+//! HTTP server on `127.0.0.1` (loopback only — this is a monitoring
+//! hook, not a remote API) serving `GET /status` with the same
+//! [`HealthStatus`](crate::bridge::HealthStatus) the UI polls plus a
+//! metrics snapshot. Disabled by default; the listener is torn down by
+//! the graceful-shutdown hook.
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_util::sync::CancellationToken;
+
+use crate::bridge::{Bridge, HealthStatus};
+use crate::error::AppError;
+use crate::metrics::MetricsSnapshot;
+
+/// Body of a `/status` response.
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    health: HealthStatus,
+    metrics: MetricsSnapshot,
+}
+
+/// Handle to the running server, managed so shutdown can stop it.
+pub struct StatusServer {
+    cancel: CancellationToken,
+}
+
+impl StatusServer {
+    /// Stop accepting connections. Idempotent.
+    pub fn stop(&self) {
+        self.cancel.cancel();
+    }
+}
+
+/// Bind the status server on `127.0.0.1:port` and serve in the
+/// background.
+///
+/// Binding happens synchronously so a taken port fails app startup
+/// with a clear error instead of a silent dead endpoint.
+pub fn start(app: &AppHandle, port: u16) -> Result<StatusServer, AppError> {
+    let listener = std::net::TcpListener::bind(("127.0.0.1", port)).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::AddrInUse {
+            AppError::Internal(format!(
+                "status server port {port} is already in use; change status_port in settings"
+            ))
+        } else {
+            AppError::Internal(format!("failed to bind status server on port {port}: {e}"))
+        }
+    })?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| AppError::Internal(format!("failed to configure status server: {e}")))?;
+
+    let cancel = CancellationToken::new();
+    let token = cancel.clone();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let listener = match tokio::net::TcpListener::from_std(listener) {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::error!("status server failed to start: {e}");
+                return;
+            }
+        };
+        tracing::info!(port, "status server listening on loopback");
+        loop {
+            let accepted = tokio::select! {
+                _ = token.cancelled() => break,
+                accepted = listener.accept() => accepted,
+            };
+            let Ok((stream, _)) = accepted else { continue };
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = handle_connection(&app, stream).await;
+            });
+        }
+        tracing::info!("status server stopped");
+    });
+    Ok(StatusServer { cancel })
+}
+
+async fn handle_connection(
+    app: &AppHandle,
+    mut stream: tokio::net::TcpStream,
+) -> std::io::Result<()> {
+    // One short request per connection; the request line is all that
+    // matters for a two-route server.
+    let mut buf = [0u8; 1024];
+    let n = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let line = request.lines().next().unwrap_or_default();
+
+    let (status, body) = if line.starts_with("GET /status ") {
+        let report = StatusReport {
+            health: app.state::<Bridge>().health().await,
+            metrics: {
+                let mut snapshot = app.state::<crate::metrics::Metrics>().snapshot();
+                snapshot.in_flight = app.state::<crate::limit::BackendGate>().in_flight();
+                snapshot
+            },
+        };
+        (
+            "200 OK",
+            serde_json::to_string(&report).unwrap_or_else(|_| "{}".into()),
+        )
+    } else {
+        ("404 Not Found", r#"{"error":"not found"}"#.into())
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
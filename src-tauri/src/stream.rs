@@ -0,0 +1,261 @@
+//! Token streaming from the presenter's `/generate` endpoint.
+//!
+//! Tokens are pushed to the frontend as `"token"` events rather than
+//! buffered into a single response, so long generations render as they
+//! arrive. Each stream carries a UUID `request_id` so the frontend can
+//! route concurrent streams to different panes. Transport follows
+//! `BridgeConfig.transport`: chunked HTTP by default, framed WebSocket
+//! when selected.
+
+use futures_util::StreamExt;
+use serde::Serialize;
+use tauri::Emitter;
+use uuid::Uuid;
+
+use crate::bridge::{Bridge, Transport};
+use crate::cancel::CancelRegistry;
+use crate::error::AppError;
+use crate::ws::{WsBridge, WsStreamEvent};
+
+/// Payload for `"token"` events.
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenEvent {
+    pub request_id: String,
+    pub text: String,
+}
+
+/// Payload for the terminal `"token-done"` / `"token-error"` /
+/// `"token-cancelled"` events.
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamEnd {
+    pub request_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct GenerateRequest<'a> {
+    prompt: &'a str,
+    /// System section of the active prompt template, when it has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    /// Lets the backend correlate a later cancel with this stream.
+    request_id: &'a str,
+}
+
+fn emit_token(window: &tauri::Window, request_id: &str, text: String) -> Result<(), AppError> {
+    window
+        .emit(
+            "token",
+            TokenEvent {
+                request_id: request_id.to_string(),
+                text,
+            },
+        )
+        .map_err(|e| AppError::Internal(format!("failed to emit token event: {e}")))
+}
+
+fn emit_done(window: &tauri::Window, request_id: &str) -> Result<(), AppError> {
+    window
+        .emit(
+            "token-done",
+            StreamEnd {
+                request_id: request_id.to_string(),
+                error: None,
+            },
+        )
+        .map_err(|e| AppError::Internal(format!("failed to emit token-done event: {e}")))
+}
+
+fn emit_cancelled(window: &tauri::Window, request_id: &str) -> Result<(), AppError> {
+    window
+        .emit(
+            "token-cancelled",
+            StreamEnd {
+                request_id: request_id.to_string(),
+                error: None,
+            },
+        )
+        .map_err(|e| AppError::Internal(format!("failed to emit token-cancelled event: {e}")))
+}
+
+/// Best-effort upstream abort over HTTP. An older backend without the
+/// cancel endpoint answers 404, which is fine — dropping the reader
+/// already stopped the flow on our side.
+async fn abort_http(bridge: &Bridge, request_id: &str) {
+    let url = format!("{}/generate/{request_id}/cancel", bridge.active_endpoint());
+    let _ = bridge.client().post(url).send().await;
+}
+
+fn emit_error(window: &tauri::Window, request_id: &str, error: String) {
+    let _ = window.emit(
+        "token-error",
+        StreamEnd {
+            request_id: request_id.to_string(),
+            error: Some(error),
+        },
+    );
+}
+
+/// Stream generated tokens for `prompt` to the calling window.
+///
+/// Emits one `"token"` event per upstream chunk, then `"token-done"` when
+/// the stream closes cleanly, `"token-error"` if the upstream breaks
+/// mid-stream, or `"token-cancelled"` when `cancel_request` aborts it.
+/// Tokens already emitted before a cancel stay with the frontend.
+#[tauri::command]
+#[tracing::instrument(skip_all, fields(request_id = tracing::field::Empty))]
+pub async fn generate_stream(
+    prompt: String,
+    window: tauri::Window,
+    bridge: tauri::State<'_, Bridge>,
+    cancels: tauri::State<'_, CancelRegistry>,
+    models: tauri::State<'_, crate::models::ModelState>,
+    ws: tauri::State<'_, WsBridge>,
+    metrics: tauri::State<'_, crate::metrics::Metrics>,
+    online: tauri::State<'_, crate::offline::OnlineState>,
+    settings: tauri::State<'_, crate::settings::SettingsStore>,
+    gate: tauri::State<'_, crate::limit::BackendGate>,
+    prompts: tauri::State<'_, crate::prompt::PromptStore>,
+) -> Result<(), AppError> {
+    online.guard()?;
+    let prompt = crate::input::validate_user_input(&prompt, settings.get().max_input_chars)?;
+    let _slot = gate.acquire().await?;
+    let request_id = Uuid::new_v4().to_string();
+    tracing::Span::current().record("request_id", request_id.as_str());
+    let model = models.active();
+    // Wrap the validated input in the active prompt template; the text
+    // goes in as data, never as template syntax.
+    let rendered = prompts.render(&prompt);
+
+    let work = async {
+        match bridge.config().transport {
+            Transport::Websocket => {
+                stream_over_ws(&rendered, model, &request_id, &window, &bridge, &cancels, &ws)
+                    .await
+            }
+            Transport::Http => {
+                stream_over_http(&rendered, model, &request_id, &window, &bridge, &cancels).await
+            }
+        }
+    };
+    let result = crate::metrics::timed(&metrics, "generate_stream", work).await;
+    online.observe(&result);
+    result
+}
+
+async fn stream_over_http(
+    rendered: &crate::prompt::RenderedPrompt,
+    model: Option<String>,
+    request_id: &str,
+    window: &tauri::Window,
+    bridge: &Bridge,
+    cancels: &CancelRegistry,
+) -> Result<(), AppError> {
+    let response = bridge
+        .post_stream(
+            "/generate",
+            &GenerateRequest {
+                prompt: &rendered.prompt,
+                system: rendered.system.as_deref(),
+                model,
+                request_id,
+            },
+        )
+        .await?;
+
+    let token = cancels.register(request_id);
+    let mut upstream = response.bytes_stream();
+    let mut cancelled = false;
+    loop {
+        let chunk = tokio::select! {
+            _ = token.cancelled() => {
+                cancelled = true;
+                break;
+            }
+            chunk = upstream.next() => match chunk {
+                Some(chunk) => chunk,
+                None => break,
+            },
+        };
+        match chunk {
+            Ok(bytes) => {
+                let text = String::from_utf8_lossy(&bytes).into_owned();
+                if text.is_empty() {
+                    continue;
+                }
+                emit_token(window, request_id, text)?;
+            }
+            Err(e) => {
+                cancels.complete(request_id);
+                emit_error(window, request_id, e.to_string());
+                return Err(AppError::BackendUnreachable(format!(
+                    "stream interrupted: {e}"
+                )));
+            }
+        }
+    }
+    cancels.complete(request_id);
+    if cancelled {
+        // Dropping the reader closes the connection; also tell the
+        // backend so it stops generating.
+        drop(upstream);
+        abort_http(bridge, request_id).await;
+        return emit_cancelled(window, request_id);
+    }
+    emit_done(window, request_id)
+}
+
+async fn stream_over_ws(
+    rendered: &crate::prompt::RenderedPrompt,
+    model: Option<String>,
+    request_id: &str,
+    window: &tauri::Window,
+    bridge: &Bridge,
+    cancels: &CancelRegistry,
+    ws: &WsBridge,
+) -> Result<(), AppError> {
+    let payload = serde_json::json!({
+        "prompt": rendered.prompt,
+        "system": rendered.system,
+        "model": model,
+    });
+    let mut events = ws
+        .stream(bridge.base_url(), request_id, "generate", payload)
+        .await?;
+
+    let token = cancels.register(request_id);
+    let mut cancelled = false;
+    loop {
+        let event = tokio::select! {
+            _ = token.cancelled() => {
+                cancelled = true;
+                break;
+            }
+            event = events.recv() => match event {
+                Some(event) => event,
+                None => break,
+            },
+        };
+        match event {
+            WsStreamEvent::Token(text) => emit_token(window, request_id, text)?,
+            WsStreamEvent::Done => break,
+            WsStreamEvent::Error(e) => {
+                cancels.complete(request_id);
+                emit_error(window, request_id, e.clone());
+                return Err(AppError::BackendUnreachable(format!(
+                    "stream interrupted: {e}"
+                )));
+            }
+        }
+    }
+    cancels.complete(request_id);
+    if cancelled {
+        // Clears the pending entry and sends a cancel frame upstream.
+        ws.abort(bridge.base_url(), request_id).await;
+        return emit_cancelled(window, request_id);
+    }
+    emit_done(window, request_id)
+}
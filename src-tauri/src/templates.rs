@@ -0,0 +1,221 @@
+//! Reusable plan templates with `{placeholder}` slots.
+//!
+//! Templates live in their own SQLite file next to the history
+//! database. Instantiation is strict: every placeholder must be filled,
+//! and substituted values are checked for shell metacharacters so a
+//! parameter can't smuggle extra arguments into the plan.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::allowlist;
+use crate::error::AppError;
+use crate::plan::Plan;
+
+/// A stored template: a plan skeleton whose command and args may
+/// contain `{placeholder}` slots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanTemplate {
+    pub name: String,
+    pub description: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// Managed template storage.
+pub struct TemplateStore {
+    conn: Mutex<Connection>,
+}
+
+impl TemplateStore {
+    pub fn open(app_data_dir: &Path) -> Result<Self, AppError> {
+        std::fs::create_dir_all(app_data_dir)
+            .map_err(|e| AppError::Storage(format!("failed to create app data dir: {e}")))?;
+        let conn = Connection::open(app_data_dir.join("templates.db"))
+            .map_err(|e| AppError::Storage(format!("failed to open template db: {e}")))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS templates (
+                name TEXT PRIMARY KEY,
+                body TEXT NOT NULL
+            )",
+        )
+        .map_err(|e| AppError::Storage(format!("failed to init template db: {e}")))?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn save(&self, template: &PlanTemplate) -> Result<(), AppError> {
+        let body = serde_json::to_string(template)
+            .map_err(|e| AppError::Internal(format!("failed to encode template: {e}")))?;
+        self.conn
+            .lock()
+            .unwrap()
+            .execute(
+                "INSERT INTO templates (name, body) VALUES (?1, ?2)
+                 ON CONFLICT(name) DO UPDATE SET body = excluded.body",
+                rusqlite::params![template.name, body],
+            )
+            .map_err(|e| AppError::Storage(format!("failed to save template: {e}")))?;
+        Ok(())
+    }
+
+    pub fn list(&self) -> Result<Vec<PlanTemplate>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT body FROM templates ORDER BY name")
+            .map_err(|e| AppError::Storage(format!("failed to query templates: {e}")))?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| AppError::Storage(format!("failed to read templates: {e}")))?;
+        let mut out = Vec::new();
+        for body in rows {
+            let body =
+                body.map_err(|e| AppError::Storage(format!("failed to read template: {e}")))?;
+            let template = serde_json::from_str(&body)
+                .map_err(|e| AppError::Storage(format!("corrupt template record: {e}")))?;
+            out.push(template);
+        }
+        Ok(out)
+    }
+
+    pub fn get(&self, name: &str) -> Result<PlanTemplate, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let body: String = conn
+            .query_row(
+                "SELECT body FROM templates WHERE name = ?1",
+                rusqlite::params![name],
+                |row| row.get(0),
+            )
+            .map_err(|_| AppError::InvalidInput(format!("no template named {name:?}")))?;
+        serde_json::from_str(&body)
+            .map_err(|e| AppError::Storage(format!("corrupt template record: {e}")))
+    }
+}
+
+/// Substitute `{placeholder}` slots in one template string.
+///
+/// Unknown placeholders are an error (the template stays unfilled), and
+/// substituted values may not contain shell metacharacters.
+fn substitute(input: &str, params: &HashMap<String, String>) -> Result<String, AppError> {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let Some(close) = after.find('}') else {
+            return Err(AppError::InvalidInput(format!(
+                "unterminated placeholder in template fragment {input:?}"
+            )));
+        };
+        let key = &after[..close];
+        let Some(value) = params.get(key) else {
+            return Err(AppError::InvalidInput(format!(
+                "unfilled placeholder {{{key}}}"
+            )));
+        };
+        if let Some(ch) = allowlist::forbidden_char(value) {
+            return Err(AppError::PolicyDenied(format!(
+                "forbidden character {ch:?} in template parameter {key:?}"
+            )));
+        }
+        out.push_str(value);
+        rest = &after[close + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Build a concrete [`Plan`] from a template and its parameters.
+pub fn instantiate(
+    template: &PlanTemplate,
+    params: &HashMap<String, String>,
+) -> Result<Plan, AppError> {
+    Ok(Plan {
+        id: Uuid::new_v4().to_string(),
+        description: substitute(&template.description, params)?,
+        command: substitute(&template.command, params)?,
+        args: template
+            .args
+            .iter()
+            .map(|arg| substitute(arg, params))
+            .collect::<Result<Vec<_>, _>>()?,
+    })
+}
+
+#[tauri::command]
+pub fn save_template(
+    template: PlanTemplate,
+    store: tauri::State<'_, TemplateStore>,
+) -> Result<(), AppError> {
+    if template.name.trim().is_empty() {
+        return Err(AppError::InvalidInput("template name is empty".into()));
+    }
+    store.save(&template)
+}
+
+#[tauri::command]
+pub fn list_templates(
+    store: tauri::State<'_, TemplateStore>,
+) -> Result<Vec<PlanTemplate>, AppError> {
+    store.list()
+}
+
+/// Fill a named template's placeholders and return the runnable plan.
+#[tauri::command]
+pub fn instantiate_template(
+    name: String,
+    params: HashMap<String, String>,
+    store: tauri::State<'_, TemplateStore>,
+) -> Result<Plan, AppError> {
+    let template = store.get(&name)?;
+    instantiate(&template, &params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> PlanTemplate {
+        PlanTemplate {
+            name: "install".into(),
+            description: "Install {package}".into(),
+            command: "apt".into(),
+            args: vec!["install".into(), "{package}".into()],
+        }
+    }
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn fills_placeholders() {
+        let plan = instantiate(&template(), &params(&[("package", "htop")])).unwrap();
+        assert_eq!(plan.command, "apt");
+        assert_eq!(plan.args, vec!["install", "htop"]);
+        assert_eq!(plan.description, "Install htop");
+    }
+
+    #[test]
+    fn rejects_unfilled_placeholder() {
+        let err = instantiate(&template(), &params(&[])).unwrap_err();
+        assert!(err.to_string().contains("unfilled placeholder"));
+    }
+
+    #[test]
+    fn rejects_injection_through_parameter() {
+        let err =
+            instantiate(&template(), &params(&[("package", "htop; rm -rf /")])).unwrap_err();
+        assert!(matches!(err, AppError::PolicyDenied(_)));
+    }
+}
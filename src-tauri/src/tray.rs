@@ -0,0 +1,113 @@
+//! System tray: quick intent entry without hunting for the window.
+//!
+//! The tray menu offers "New request" (focus the window and the input
+//! field), a "Recent plans" submenu built from the tail of the audit
+//! log, and "Quit". The menu is rebuilt after every executed plan so
+//! the recent list stays current.
+
+use std::sync::Mutex;
+
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::{TrayIcon, TrayIconBuilder};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::audit::AuditLog;
+use crate::error::AppError;
+
+/// How many audit entries feed the "Recent plans" submenu.
+const RECENT_PLAN_LIMIT: u32 = 5;
+
+/// Managed handle to the tray icon so the menu can be swapped later.
+pub struct TrayState {
+    tray: Mutex<Option<TrayIcon>>,
+}
+
+fn build_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, AppError> {
+    let internal = |e: tauri::Error| AppError::Internal(format!("failed to build tray menu: {e}"));
+
+    let new_request =
+        MenuItem::with_id(app, "new-request", "New request", true, None::<&str>).map_err(internal)?;
+
+    let recent = Submenu::with_id(app, "recent-plans", "Recent plans", true).map_err(internal)?;
+    let entries = app
+        .state::<AuditLog>()
+        .tail(RECENT_PLAN_LIMIT)
+        .unwrap_or_default();
+    if entries.is_empty() {
+        let empty = MenuItem::with_id(app, "recent-empty", "No plans yet", false, None::<&str>)
+            .map_err(internal)?;
+        recent.append(&empty).map_err(internal)?;
+    }
+    for entry in &entries {
+        let label = format!("{} {}", entry.command, entry.args.join(" "));
+        let label = if label.chars().count() > 48 {
+            format!("{}…", label.chars().take(47).collect::<String>())
+        } else {
+            label
+        };
+        let item = MenuItem::with_id(
+            app,
+            format!("plan:{}", entry.plan_id),
+            label,
+            true,
+            None::<&str>,
+        )
+        .map_err(internal)?;
+        recent.append(&item).map_err(internal)?;
+    }
+
+    let separator = PredefinedMenuItem::separator(app).map_err(internal)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>).map_err(internal)?;
+
+    Menu::with_items(app, &[&new_request, &recent, &separator, &quit]).map_err(internal)
+}
+
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    match id {
+        "new-request" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            let _ = app.emit("focus-input", ());
+        }
+        "quit" => app.exit(0),
+        other => {
+            if let Some(plan_id) = other.strip_prefix("plan:") {
+                let _ = app.emit("tray-plan-selected", plan_id.to_string());
+            }
+        }
+    }
+}
+
+/// Build the tray icon and manage its handle. Called once from setup.
+pub fn init(app: &AppHandle) -> Result<(), AppError> {
+    let menu = build_menu(app)?;
+    let mut builder = TrayIconBuilder::with_id("main")
+        .menu(&menu)
+        .show_menu_on_left_click(true)
+        .on_menu_event(|app, event| handle_menu_event(app, event.id().as_ref()));
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    let tray = builder
+        .build(app)
+        .map_err(|e| AppError::Internal(format!("failed to create tray icon: {e}")))?;
+    app.manage(TrayState {
+        tray: Mutex::new(Some(tray)),
+    });
+    Ok(())
+}
+
+/// Rebuild the menu so "Recent plans" reflects the latest audit tail.
+pub fn refresh(app: &AppHandle) {
+    let Some(state) = app.try_state::<TrayState>() else {
+        return;
+    };
+    let Ok(menu) = build_menu(app) else {
+        return;
+    };
+    if let Some(tray) = state.tray.lock().unwrap().as_ref() {
+        let _ = tray.set_menu(Some(menu));
+    }
+}
@@ -0,0 +1,300 @@
+//! WebSocket transport for the bridge.
+//!
+//! When `BridgeConfig.transport` selects WebSocket, classify and
+//! generate requests are sent as framed JSON over one persistent
+//! connection and correlated back by request id. The connection is
+//! re-established lazily before the next call if it drops.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use futures_util::stream::{SplitSink, StreamExt};
+use futures_util::SinkExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::error::AppError;
+
+type WsSink = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
+
+/// Outbound frame: one request.
+#[derive(Debug, Serialize)]
+struct WsRequest<'a> {
+    request_id: &'a str,
+    op: &'a str,
+    payload: Value,
+}
+
+/// Inbound frame: a response, token, terminator, or error for a
+/// previously sent request id.
+#[derive(Debug, Deserialize)]
+struct WsResponse {
+    request_id: String,
+    kind: WsKind,
+    #[serde(default)]
+    payload: Value,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum WsKind {
+    Result,
+    Token,
+    Done,
+    Error,
+}
+
+/// Event delivered to streaming consumers.
+#[derive(Debug)]
+pub enum WsStreamEvent {
+    Token(String),
+    Done,
+    Error(String),
+}
+
+enum Pending {
+    /// Request/response call waiting for a single `result` frame.
+    Once(oneshot::Sender<Result<Value, String>>),
+    /// Streaming call receiving `token` frames until `done`/`error`.
+    Stream(mpsc::UnboundedSender<WsStreamEvent>),
+}
+
+/// Pending entries are tagged with the generation of the connection
+/// that carried them (0 until first sent), so a dying reader can fail
+/// exactly the requests that were in flight on its own socket and
+/// leave entries re-sent on a replacement connection alone.
+type PendingMap = Arc<Mutex<HashMap<String, (u64, Pending)>>>;
+
+/// Managed WebSocket connection state.
+#[derive(Default)]
+pub struct WsBridge {
+    sink: tokio::sync::Mutex<Option<(u64, WsSink)>>,
+    pending: PendingMap,
+    /// Monotonic connection counter; each dial gets the next value.
+    generation: AtomicU64,
+}
+
+impl WsBridge {
+    /// Derive the `/ws` URL from an HTTP endpoint.
+    fn ws_url(endpoint: &str) -> String {
+        let scheme_swapped = endpoint
+            .replacen("https://", "wss://", 1)
+            .replacen("http://", "ws://", 1);
+        format!("{scheme_swapped}/ws")
+    }
+
+    /// Ensure a live connection, dialing (or re-dialing) if needed.
+    async fn ensure_connected(&self, endpoint: &str) -> Result<(), AppError> {
+        let mut sink_slot = self.sink.lock().await;
+        if sink_slot.is_some() {
+            return Ok(());
+        }
+
+        let url = Self::ws_url(endpoint);
+        let (socket, _) = tokio_tungstenite::connect_async(&url).await.map_err(|e| {
+            AppError::BackendUnreachable(format!(
+                "backend does not answer on the WebSocket endpoint {url}: {e}"
+            ))
+        })?;
+        let (sink, mut reader) = socket.split();
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        *sink_slot = Some((generation, sink));
+
+        // Reader task: route frames to whoever is waiting on them. When
+        // the socket drops, fail the calls in flight on this connection
+        // so the next call reconnects.
+        let pending = Arc::clone(&self.pending);
+        tauri::async_runtime::spawn(async move {
+            while let Some(message) = reader.next().await {
+                let text = match message {
+                    Ok(Message::Text(text)) => text,
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    Ok(_) => continue,
+                };
+                let Ok(frame) = serde_json::from_str::<WsResponse>(&text) else {
+                    continue;
+                };
+                let mut pending = pending.lock().unwrap();
+                match frame.kind {
+                    WsKind::Result => {
+                        if let Some((_, Pending::Once(tx))) = pending.remove(&frame.request_id) {
+                            let _ = tx.send(Ok(frame.payload));
+                        }
+                    }
+                    WsKind::Token => {
+                        if let Some((_, Pending::Stream(tx))) = pending.get(&frame.request_id) {
+                            let token = frame
+                                .payload
+                                .as_str()
+                                .map(str::to_owned)
+                                .unwrap_or_else(|| frame.payload.to_string());
+                            let _ = tx.send(WsStreamEvent::Token(token));
+                        }
+                    }
+                    WsKind::Done => {
+                        if let Some((_, Pending::Stream(tx))) = pending.remove(&frame.request_id) {
+                            let _ = tx.send(WsStreamEvent::Done);
+                        }
+                    }
+                    WsKind::Error => {
+                        let error = frame.error.unwrap_or_else(|| "upstream error".into());
+                        match pending.remove(&frame.request_id) {
+                            Some((_, Pending::Once(tx))) => {
+                                let _ = tx.send(Err(error));
+                            }
+                            Some((_, Pending::Stream(tx))) => {
+                                let _ = tx.send(WsStreamEvent::Error(error));
+                            }
+                            None => {}
+                        }
+                    }
+                }
+            }
+            // Socket is gone: fail only the requests this connection
+            // was carrying. Entries that were never sent (generation
+            // 0) or that a send_frame retry already moved to a newer
+            // connection stay pending.
+            let mut pending = pending.lock().unwrap();
+            let stale: Vec<String> = pending
+                .iter()
+                .filter(|(_, (sent_on, _))| *sent_on == generation)
+                .map(|(id, _)| id.clone())
+                .collect();
+            for id in stale {
+                match pending.remove(&id) {
+                    Some((_, Pending::Once(tx))) => {
+                        let _ = tx.send(Err("websocket connection lost".into()));
+                    }
+                    Some((_, Pending::Stream(tx))) => {
+                        let _ = tx.send(WsStreamEvent::Error(
+                            "websocket connection lost".into(),
+                        ));
+                    }
+                    None => {}
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Record which connection a request actually went out on, so the
+    /// matching reader's teardown fails it and no other reader does.
+    fn mark_sent(&self, request_id: &str, generation: u64) {
+        if let Some(entry) = self.pending.lock().unwrap().get_mut(request_id) {
+            entry.0 = generation;
+        }
+    }
+
+    async fn send_frame(
+        &self,
+        endpoint: &str,
+        request_id: &str,
+        op: &str,
+        payload: Value,
+    ) -> Result<(), AppError> {
+        self.ensure_connected(endpoint).await?;
+        let frame = serde_json::to_string(&WsRequest {
+            request_id,
+            op,
+            payload,
+        })
+        .map_err(|e| AppError::Internal(format!("failed to encode ws frame: {e}")))?;
+
+        let mut sink_slot = self.sink.lock().await;
+        if let Some((generation, sink)) = sink_slot.as_mut() {
+            if sink.send(Message::Text(frame.clone())).await.is_ok() {
+                let generation = *generation;
+                drop(sink_slot);
+                self.mark_sent(request_id, generation);
+                return Ok(());
+            }
+        }
+        // Stale connection: reconnect once and retry the send.
+        *sink_slot = None;
+        drop(sink_slot);
+        self.ensure_connected(endpoint).await?;
+        let mut sink_slot = self.sink.lock().await;
+        let (generation, sink) = sink_slot
+            .as_mut()
+            .ok_or_else(|| AppError::BackendUnreachable("websocket reconnect failed".into()))?;
+        let generation = *generation;
+        sink.send(Message::Text(frame))
+            .await
+            .map_err(|e| AppError::BackendUnreachable(format!("websocket send failed: {e}")))?;
+        drop(sink_slot);
+        self.mark_sent(request_id, generation);
+        Ok(())
+    }
+
+    /// Request/response call over the socket.
+    pub async fn call(
+        &self,
+        endpoint: &str,
+        request_id: &str,
+        op: &str,
+        payload: Value,
+    ) -> Result<Value, AppError> {
+        let (tx, rx) = oneshot::channel();
+        self.pending
+            .lock()
+            .unwrap()
+            .insert(request_id.to_string(), (0, Pending::Once(tx)));
+
+        if let Err(e) = self.send_frame(endpoint, request_id, op, payload).await {
+            self.pending.lock().unwrap().remove(request_id);
+            return Err(e);
+        }
+
+        match rx.await {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(message)) => Err(AppError::Internal(message)),
+            Err(_) => Err(AppError::BackendUnreachable(
+                "websocket connection lost".into(),
+            )),
+        }
+    }
+
+    /// Streaming call: returns a receiver of token/done/error events.
+    pub async fn stream(
+        &self,
+        endpoint: &str,
+        request_id: &str,
+        op: &str,
+        payload: Value,
+    ) -> Result<mpsc::UnboundedReceiver<WsStreamEvent>, AppError> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.pending
+            .lock()
+            .unwrap()
+            .insert(request_id.to_string(), (0, Pending::Stream(tx)));
+
+        if let Err(e) = self.send_frame(endpoint, request_id, op, payload).await {
+            self.pending.lock().unwrap().remove(request_id);
+            return Err(e);
+        }
+        Ok(rx)
+    }
+
+    /// Drop a stream's pending entry (e.g. after the consumer stops).
+    pub fn forget(&self, request_id: &str) {
+        self.pending.lock().unwrap().remove(request_id);
+    }
+
+    /// Fire-and-forget cancel frame for an in-flight stream, so the
+    /// backend can stop generating instead of talking to a dead
+    /// receiver. Best effort: a send failure just means the connection
+    /// is already gone.
+    pub async fn abort(&self, endpoint: &str, request_id: &str) {
+        self.forget(request_id);
+        let _ = self
+            .send_frame(endpoint, request_id, "cancel", Value::Null)
+            .await;
+    }
+}